@@ -535,7 +535,11 @@ fn later_use_kind(
                 let block = &self.body.basic_blocks[location.block];
 
                 let kind = if let Some(&Statement {
-                    kind: StatementKind::FakeRead(box (FakeReadCause::ForLet(_), place)),
+                    kind:
+                        StatementKind::FakeRead(box (
+                            FakeReadCause::ForLet(_) | FakeReadCause::ForLetElse(_),
+                            place,
+                        )),
                     ..
                 }) = block.statements.get(location.statement_index)
                 {
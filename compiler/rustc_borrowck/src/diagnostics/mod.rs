@@ -13,9 +13,8 @@
 use rustc_infer::infer::BoundRegionConversionTime;
 use rustc_middle::mir::tcx::PlaceTy;
 use rustc_middle::mir::{
-    AggregateKind, CallSource, ConstOperand, FakeReadCause, Local, LocalInfo, LocalKind, Location,
-    Operand, Place, PlaceRef, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
-    TerminatorKind,
+    AggregateKind, CallSource, ConstOperand, Local, LocalInfo, LocalKind, Location, Operand, Place,
+    PlaceRef, ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
 };
 use rustc_middle::ty::print::Print;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
@@ -797,24 +796,15 @@ pub(super) fn move_spans(
 
         // StatementKind::FakeRead only contains a def_id if they are introduced as a result
         // of pattern matching within a closure.
-        if let StatementKind::FakeRead(box (cause, place)) = stmt.kind {
-            match cause {
-                FakeReadCause::ForMatchedPlace(Some(closure_def_id))
-                | FakeReadCause::ForLet(Some(closure_def_id)) => {
-                    debug!("move_spans: def_id={:?} place={:?}", closure_def_id, place);
-                    let places = &[Operand::Move(place)];
-                    if let Some((args_span, coroutine_kind, capture_kind_span, path_span)) =
-                        self.closure_span(closure_def_id, moved_place, IndexSlice::from_raw(places))
-                    {
-                        return ClosureUse {
-                            coroutine_kind,
-                            args_span,
-                            capture_kind_span,
-                            path_span,
-                        };
-                    }
-                }
-                _ => {}
+        if let StatementKind::FakeRead(box (cause, place)) = stmt.kind
+            && let Some(closure_def_id) = cause.closure_def_id()
+        {
+            debug!("move_spans: def_id={:?} place={:?}", closure_def_id, place);
+            let places = &[Operand::Move(place)];
+            if let Some((args_span, coroutine_kind, capture_kind_span, path_span)) =
+                self.closure_span(closure_def_id, moved_place, IndexSlice::from_raw(places))
+            {
+                return ClosureUse { coroutine_kind, args_span, capture_kind_span, path_span };
             }
         }
 
@@ -1371,7 +1371,17 @@ fn check_terminator(
                 }
                 // FIXME: check the values
             }
-            TerminatorKind::Call { func, args, destination, call_source, target, .. } => {
+            TerminatorKind::Call { func, args, .. }
+            | TerminatorKind::TailCall { func, args, .. } => {
+                // A tail call behaves like a call writing directly into the return place,
+                // immediately followed by a return.
+                let (destination, converges, call_source) = match term.kind {
+                    TerminatorKind::Call { destination, target, call_source, .. } => {
+                        (destination, target.is_some(), call_source)
+                    }
+                    _ => (Place::return_place(), true, CallSource::Normal),
+                };
+
                 self.check_operand(func, term_location);
                 for arg in args {
                     self.check_operand(arg, term_location);
@@ -1428,7 +1438,7 @@ fn check_terminator(
                     ConstraintCategory::Boring,
                 );
                 let sig = self.normalize(sig, term_location);
-                self.check_call_dest(body, term, &sig, *destination, *target, term_location);
+                self.check_call_dest(body, term, &sig, destination, converges, term_location);
 
                 // The ordinary liveness rules will ensure that all
                 // regions in the type of the callee are live here. We
@@ -1446,7 +1456,7 @@ fn check_terminator(
                         .add_element(region_vid, term_location);
                 }
 
-                self.check_call_inputs(body, term, func, &sig, args, term_location, *call_source);
+                self.check_call_inputs(body, term, func, &sig, args, term_location, call_source);
             }
             TerminatorKind::Assert { cond, msg, .. } => {
                 self.check_operand(cond, term_location);
@@ -1499,69 +1509,63 @@ fn check_call_dest(
         term: &Terminator<'tcx>,
         sig: &ty::FnSig<'tcx>,
         destination: Place<'tcx>,
-        target: Option<BasicBlock>,
+        converges: bool,
         term_location: Location,
     ) {
         let tcx = self.tcx();
-        match target {
-            Some(_) => {
-                let dest_ty = destination.ty(body, tcx).ty;
-                let dest_ty = self.normalize(dest_ty, term_location);
-                let category = match destination.as_local() {
-                    Some(RETURN_PLACE) => {
-                        if let BorrowCheckContext {
-                            universal_regions:
-                                UniversalRegions {
-                                    defining_ty:
-                                        DefiningTy::Const(def_id, _)
-                                        | DefiningTy::InlineConst(def_id, _),
-                                    ..
-                                },
-                            ..
-                        } = self.borrowck_context
-                        {
-                            if tcx.is_static(*def_id) {
-                                ConstraintCategory::UseAsStatic
-                            } else {
-                                ConstraintCategory::UseAsConst
-                            }
+        if converges {
+            let dest_ty = destination.ty(body, tcx).ty;
+            let dest_ty = self.normalize(dest_ty, term_location);
+            let category = match destination.as_local() {
+                Some(RETURN_PLACE) => {
+                    if let BorrowCheckContext {
+                        universal_regions:
+                            UniversalRegions {
+                                defining_ty:
+                                    DefiningTy::Const(def_id, _) | DefiningTy::InlineConst(def_id, _),
+                                ..
+                            },
+                        ..
+                    } = self.borrowck_context
+                    {
+                        if tcx.is_static(*def_id) {
+                            ConstraintCategory::UseAsStatic
                         } else {
-                            ConstraintCategory::Return(ReturnConstraint::Normal)
+                            ConstraintCategory::UseAsConst
                         }
+                    } else {
+                        ConstraintCategory::Return(ReturnConstraint::Normal)
                     }
-                    Some(l) if !body.local_decls[l].is_user_variable() => {
-                        ConstraintCategory::Boring
-                    }
-                    _ => ConstraintCategory::Assignment,
-                };
+                }
+                Some(l) if !body.local_decls[l].is_user_variable() => ConstraintCategory::Boring,
+                _ => ConstraintCategory::Assignment,
+            };
 
-                let locations = term_location.to_locations();
+            let locations = term_location.to_locations();
 
-                if let Err(terr) = self.sub_types(sig.output(), dest_ty, locations, category) {
-                    span_mirbug!(
-                        self,
-                        term,
-                        "call dest mismatch ({:?} <- {:?}): {:?}",
-                        dest_ty,
-                        sig.output(),
-                        terr
-                    );
-                }
+            if let Err(terr) = self.sub_types(sig.output(), dest_ty, locations, category) {
+                span_mirbug!(
+                    self,
+                    term,
+                    "call dest mismatch ({:?} <- {:?}): {:?}",
+                    dest_ty,
+                    sig.output(),
+                    terr
+                );
+            }
 
-                // When `unsized_fn_params` and `unsized_locals` are both not enabled,
-                // this check is done at `check_local`.
-                if self.unsized_feature_enabled() {
-                    let span = term.source_info.span;
-                    self.ensure_place_sized(dest_ty, span);
-                }
+            // When `unsized_fn_params` and `unsized_locals` are both not enabled,
+            // this check is done at `check_local`.
+            if self.unsized_feature_enabled() {
+                let span = term.source_info.span;
+                self.ensure_place_sized(dest_ty, span);
             }
-            None => {
-                // The signature in this call can reference region variables,
-                // so erase them before calling a query.
-                let output_ty = self.tcx().erase_regions(sig.output());
-                if !output_ty.is_privately_uninhabited(self.tcx(), self.param_env) {
-                    span_mirbug!(self, term, "call to converging function {:?} w/o dest", sig);
-                }
+        } else {
+            // The signature in this call can reference region variables,
+            // so erase them before calling a query.
+            let output_ty = self.tcx().erase_regions(sig.output());
+            if !output_ty.is_privately_uninhabited(self.tcx(), self.param_env) {
+                span_mirbug!(self, term, "call to converging function {:?} w/o dest", sig);
             }
         }
     }
@@ -1650,6 +1654,11 @@ fn check_iscleanup(&mut self, body: &Body<'tcx>, block_data: &BasicBlockData<'tc
                     span_mirbug!(self, block_data, "return on cleanup block")
                 }
             }
+            TerminatorKind::TailCall { .. } => {
+                if is_cleanup {
+                    span_mirbug!(self, block_data, "tailcall on cleanup block")
+                }
+            }
             TerminatorKind::CoroutineDrop { .. } => {
                 if is_cleanup {
                     span_mirbug!(self, block_data, "coroutine_drop in cleanup block")
@@ -1814,6 +1823,9 @@ fn aggregate_field_ty(
             AggregateKind::Tuple => {
                 unreachable!("This should have been covered in check_rvalues");
             }
+            AggregateKind::RawPtr(..) => {
+                unreachable!("RawPtr should only be in runtime MIR");
+            }
         }
     }
 
@@ -2378,7 +2390,7 @@ fn check_rvalue(&mut self, body: &Body<'tcx>, rvalue: &Rvalue<'tcx>, location: L
             | Rvalue::ThreadLocalRef(..)
             | Rvalue::Len(..)
             | Rvalue::Discriminant(..)
-            | Rvalue::NullaryOp(NullOp::OffsetOf(..), _) => {}
+            | Rvalue::NullaryOp(NullOp::OffsetOf(..) | NullOp::UbChecks, _) => {}
         }
     }
 
@@ -2408,6 +2420,7 @@ fn rvalue_user_ty(&self, rvalue: &Rvalue<'tcx>) -> Option<UserTypeAnnotationInde
                 AggregateKind::Tuple => None,
                 AggregateKind::Closure(_, _) => None,
                 AggregateKind::Coroutine(_, _, _) => None,
+                AggregateKind::RawPtr(_, _) => None,
             },
         }
     }
@@ -2429,6 +2442,10 @@ fn check_aggregate_rvalue(
             return;
         }
 
+        if let AggregateKind::RawPtr(..) = aggregate_kind {
+            bug!("RawPtr should only be in runtime MIR");
+        }
+
         for (i, operand) in operands.iter_enumerated() {
             let field_ty = match self.aggregate_field_ty(aggregate_kind, i, location) {
                 Ok(field_ty) => field_ty,
@@ -2639,7 +2656,7 @@ fn prove_aggregate_predicates(
                 (def_id, self.prove_closure_bounds(tcx, def_id.expect_local(), args, location))
             }
 
-            AggregateKind::Array(_) | AggregateKind::Tuple => {
+            AggregateKind::Array(_) | AggregateKind::Tuple | AggregateKind::RawPtr(..) => {
                 (CRATE_DEF_ID.to_def_id(), ty::InstantiatedPredicates::empty())
             }
         };
@@ -441,6 +441,9 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                     )
                 });
             }
+            TerminatorKind::TailCall { .. } => {
+                fx.tcx.sess.span_fatal(source_info.span, "tail calls are not yet supported");
+            }
             TerminatorKind::InlineAsm {
                 template,
                 operands,
@@ -768,6 +771,15 @@ fn is_fat_ptr<'tcx>(fx: &FunctionCx<'_, '_, 'tcx>, ty: Ty<'tcx>) -> bool {
                         NullOp::OffsetOf(fields) => {
                             layout.offset_of_subfield(fx, fields.iter()).bytes()
                         }
+                        NullOp::UbChecks => {
+                            let val = fx.tcx.sess.opts.debug_assertions;
+                            let val = CValue::by_val(
+                                fx.bcx.ins().iconst(types::I8, i64::from(val)),
+                                fx.layout_of(fx.tcx.types.bool),
+                            );
+                            lval.write_cvalue(fx, val);
+                            return;
+                        }
                     };
                     let val = CValue::by_val(
                         fx.bcx.ins().iconst(fx.pointer_type, i64::try_from(val).unwrap()),
@@ -775,6 +787,21 @@ fn is_fat_ptr<'tcx>(fx: &FunctionCx<'_, '_, 'tcx>, ty: Ty<'tcx>) -> bool {
                     );
                     lval.write_cvalue(fx, val);
                 }
+                Rvalue::Aggregate(ref kind, ref operands)
+                    if matches!(**kind, mir::AggregateKind::RawPtr(..)) =>
+                {
+                    let [data, meta] = &*operands.raw else {
+                        bug!("RawPtr fields: {operands:?}");
+                    };
+                    let data = codegen_operand(fx, data).load_scalar(fx);
+                    let meta = codegen_operand(fx, meta);
+                    let ptr = if meta.layout().is_zst() {
+                        CValue::by_val(data, lval.layout())
+                    } else {
+                        CValue::by_val_pair(data, meta.load_scalar(fx), lval.layout())
+                    };
+                    lval.write_cvalue(fx, ptr);
+                }
                 Rvalue::Aggregate(ref kind, ref operands) => {
                     let (variant_index, variant_dest, active_field_index) = match **kind {
                         mir::AggregateKind::Adt(_, variant_index, _, _, active_field_index) => {
@@ -525,6 +525,7 @@ pub(crate) fn mir_operand_get_const_val<'tcx>(
                     | TerminatorKind::UnwindResume
                     | TerminatorKind::UnwindTerminate(_)
                     | TerminatorKind::Return
+                    | TerminatorKind::TailCall { .. }
                     | TerminatorKind::Unreachable
                     | TerminatorKind::Drop { .. }
                     | TerminatorKind::Assert { .. } => {}
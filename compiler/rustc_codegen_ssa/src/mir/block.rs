@@ -1264,6 +1264,12 @@ fn codegen_terminator(
                 fn_span,
                 mergeable_succ(),
             ),
+            mir::TerminatorKind::TailCall { .. } => {
+                span_bug!(
+                    terminator.source_info.span,
+                    "tail calls are not yet supported in codegen"
+                )
+            }
             mir::TerminatorKind::CoroutineDrop | mir::TerminatorKind::Yield { .. } => {
                 bug!("coroutine ops in codegen")
             }
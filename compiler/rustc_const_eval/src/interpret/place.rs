@@ -985,6 +985,22 @@ pub fn write_aggregate(
         operands: &IndexSlice<FieldIdx, mir::Operand<'tcx>>,
         dest: &PlaceTy<'tcx, M::Provenance>,
     ) -> InterpResult<'tcx> {
+        if let mir::AggregateKind::RawPtr(..) = kind {
+            // Raw pointers are not laid out as their fields, so build the immediate directly.
+            let [data, meta] = &*operands.raw else {
+                bug!("RawPtr fields: {operands:?}");
+            };
+            let data = self.read_pointer(&self.eval_operand(data, None)?)?;
+            let meta = self.eval_operand(meta, None)?;
+            let meta = if meta.layout.is_zst() {
+                MemPlaceMeta::None
+            } else {
+                MemPlaceMeta::Meta(self.read_scalar(&meta)?)
+            };
+            let ptr = Immediate::new_pointer_with_meta(data, meta, self);
+            return self.copy_op(&ImmTy::from_immediate(ptr, dest.layout), dest, false);
+        }
+
         self.write_uninit(dest)?;
         let (variant_index, variant_dest, active_field_index) = match *kind {
             mir::AggregateKind::Adt(_, variant_index, _, _, active_field_index) => {
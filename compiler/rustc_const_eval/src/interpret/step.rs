@@ -277,6 +277,11 @@ pub fn eval_rvalue_into_place(
                     mir::NullOp::OffsetOf(fields) => {
                         layout.offset_of_subfield(self, fields.iter()).bytes()
                     }
+                    mir::NullOp::UbChecks => {
+                        let ub_checks = self.tcx.sess.opts.debug_assertions;
+                        self.write_scalar(Scalar::from_bool(ub_checks), &dest)?;
+                        return Ok(());
+                    }
                 };
                 self.write_scalar(Scalar::from_target_usize(val, self), &dest)?;
             }
@@ -214,6 +214,9 @@ pub(super) fn eval_terminator(
                 return Ok(());
             }
 
+            // Guaranteed tail calls are not yet supported by the interpreter.
+            TailCall { .. } => throw_unsup_format!("tail calls are not supported"),
+
             // It is UB to ever encounter this.
             Unreachable => throw_ub!(Unreachable),
 
@@ -566,7 +566,10 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
 
             Rvalue::Cast(_, _, _) => {}
 
-            Rvalue::NullaryOp(NullOp::SizeOf | NullOp::AlignOf | NullOp::OffsetOf(_), _) => {}
+            Rvalue::NullaryOp(
+                NullOp::SizeOf | NullOp::AlignOf | NullOp::OffsetOf(_) | NullOp::UbChecks,
+                _,
+            ) => {}
             Rvalue::ShallowInitBox(_, _) => {}
 
             Rvalue::UnaryOp(_, operand) => {
@@ -708,7 +711,14 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
         self.super_terminator(terminator, location);
 
         match &terminator.kind {
-            TerminatorKind::Call { func, args, fn_span, call_source, .. } => {
+            TerminatorKind::Call { func, args, fn_span, .. }
+            | TerminatorKind::TailCall { func, args, fn_span, .. } => {
+                let call_source = match terminator.kind {
+                    TerminatorKind::Call { call_source, .. } => call_source,
+                    TerminatorKind::TailCall { .. } => CallSource::Normal,
+                    _ => unreachable!(),
+                };
+
                 let ConstCx { tcx, body, param_env, .. } = *self.ccx;
                 let caller = self.def_id();
 
@@ -762,7 +772,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                             callee,
                             args: fn_args,
                             span: *fn_span,
-                            call_source: *call_source,
+                            call_source,
                             feature: Some(sym::const_trait_impl),
                         });
                         return;
@@ -801,7 +811,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                                     callee,
                                     args: fn_args,
                                     span: *fn_span,
-                                    call_source: *call_source,
+                                    call_source,
                                     feature: None,
                                 });
 
@@ -817,7 +827,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                                     callee,
                                     args: fn_args,
                                     span: *fn_span,
-                                    call_source: *call_source,
+                                    call_source,
                                     feature: None,
                                 });
                                 return;
@@ -871,7 +881,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                                     callee,
                                     args: fn_args,
                                     span: *fn_span,
-                                    call_source: *call_source,
+                                    call_source,
                                     feature: None,
                                 });
                                 return;
@@ -931,7 +941,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                             callee,
                             args: fn_args,
                             span: *fn_span,
-                            call_source: *call_source,
+                            call_source,
                             feature: None,
                         });
                         return;
@@ -518,6 +518,8 @@ fn validate_rvalue(&mut self, rvalue: &Rvalue<'tcx>) -> Result<(), Unpromotable>
                 NullOp::SizeOf => {}
                 NullOp::AlignOf => {}
                 NullOp::OffsetOf(_) => {}
+                // Depends on the crate the promoted ends up being codegened in.
+                NullOp::UbChecks => return Err(Unpromotable),
             },
 
             Rvalue::ShallowInitBox(_, _) => return Err(Unpromotable),
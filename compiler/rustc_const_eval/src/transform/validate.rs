@@ -5,10 +5,12 @@
 use rustc_index::IndexVec;
 use rustc_infer::traits::Reveal;
 use rustc_middle::mir::interpret::Scalar;
-use rustc_middle::mir::visit::{NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::visit::{
+    MutatingUseContext, NonMutatingUseContext, NonUseContext, PlaceContext, Visitor,
+};
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, InstanceDef, ParamEnv, Ty, TyCtxt, TypeVisitableExt, Variance};
-use rustc_mir_dataflow::impls::MaybeStorageLive;
+use rustc_mir_dataflow::impls::{MaybeLiveLocals, MaybeStorageLive};
 use rustc_mir_dataflow::storage::always_storage_live_locals;
 use rustc_mir_dataflow::{Analysis, ResultsCursor};
 use rustc_target::abi::{Size, FIRST_VARIANT};
@@ -90,6 +92,8 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         };
         cfg_checker.visit_body(body);
         cfg_checker.check_cleanup_control_flow();
+        cfg_checker.check_two_phase_borrows();
+        cfg_checker.check_drop_order();
 
         // Also run the TypeChecker.
         for (location, msg) in validate_types(tcx, self.mir_phase, param_env, body) {
@@ -255,6 +259,221 @@ fn check_cleanup_control_flow(&self) {
         }
     }
 
+    /// Checks that every two-phase borrow `TEMP = &mut P` is assigned to a local, is activated by
+    /// at most one later use of `TEMP`, and that `P` is neither mutated nor moved between the
+    /// reservation and the activation.
+    fn check_two_phase_borrows(&self) {
+        // Two-phase borrows only matter to borrowck; later phases may freely duplicate the uses
+        // of the temporary.
+        if let MirPhase::Runtime(_) = self.mir_phase {
+            return;
+        }
+
+        let mut reservations = Vec::new();
+        for (block, data) in self.body.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let location = Location { block, statement_index };
+                let StatementKind::Assign(box (dest, Rvalue::Ref(_, kind, borrowed))) =
+                    &statement.kind
+                else {
+                    continue;
+                };
+                if !kind.allows_two_phase_borrow() {
+                    continue;
+                }
+                let Some(temp) = dest.as_local() else {
+                    self.fail(location, format!("two-phase borrow assigned to non-local {dest:?}"));
+                    continue;
+                };
+                if reservations.iter().any(|&(_, other, _)| other == temp) {
+                    self.fail(
+                        location,
+                        format!("two-phase borrow temporary {temp:?} is reserved more than once"),
+                    );
+                    continue;
+                }
+                // Borrowck ignores borrows through raw pointers, so we must as well.
+                let through_raw_ptr = borrowed.iter_projections().any(|(base, elem)| {
+                    elem == ProjectionElem::Deref && base.ty(self.body, self.tcx).ty.is_unsafe_ptr()
+                });
+                if !through_raw_ptr {
+                    reservations.push((location, temp, *borrowed));
+                }
+            }
+        }
+        if reservations.is_empty() {
+            return;
+        }
+
+        // A reservation is pending at a location iff its temporary is live both before and after
+        // that location: the reservation itself defines the temporary, and the activation is its
+        // last use.
+        let mut liveness = MaybeLiveLocals
+            .into_engine(self.tcx, self.body)
+            .iterate_to_fixpoint()
+            .into_results_cursor(self.body);
+        let mut activations = FxHashMap::default();
+        let mut pending = Vec::new();
+        for (block, data) in self.body.basic_blocks.iter_enumerated() {
+            // Liveness is a backward analysis, so walk the block back to front.
+            for statement_index in (0..=data.statements.len()).rev() {
+                let location = Location { block, statement_index };
+                let mut uses = PlaceUses(Vec::new());
+                data.visitable(statement_index).apply(location, &mut uses);
+
+                for &(reserve, temp, _) in &reservations {
+                    let activates = uses.0.iter().any(|&(place, context)| {
+                        place.local == temp
+                            && context.is_use()
+                            && !(location == reserve
+                                && context == PlaceContext::MutatingUse(MutatingUseContext::Store))
+                    });
+                    if !activates {
+                        continue;
+                    }
+                    if let Some(other) = activations.insert(temp, location) {
+                        self.fail(
+                            location,
+                            format!(
+                                "found two uses for two-phase borrow temporary {temp:?}: \
+                                 {location:?} and {other:?}"
+                            ),
+                        );
+                    }
+                }
+
+                liveness.seek_before_primary_effect(location);
+                pending.clear();
+                pending.extend(
+                    reservations.iter().copied().filter(|&(_, temp, _)| liveness.contains(temp)),
+                );
+                liveness.seek_after_primary_effect(location);
+                for &(reserve, temp, borrowed) in &pending {
+                    if !liveness.contains(temp) {
+                        continue;
+                    }
+                    for &(place, context) in &uses.0 {
+                        if self.conflicts_with_reservation(place, context, borrowed) {
+                            self.fail(
+                                location,
+                                format!(
+                                    "{place:?} is used as {context:?} while the two-phase borrow \
+                                     of {borrowed:?} reserved at {reserve:?} into {temp:?} is \
+                                     not yet activated"
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether using `place` in `context` conflicts with a pending two-phase borrow of `borrowed`.
+    ///
+    /// Like borrowck's `places_conflict`, a shallow write to a place or a drop of it does not
+    /// touch what lies behind a reference stored in it, so it does not conflict with a borrow
+    /// through that reference.
+    fn conflicts_with_reservation(
+        &self,
+        place: Place<'tcx>,
+        context: PlaceContext,
+        borrowed: Place<'tcx>,
+    ) -> bool {
+        let shallow = match context {
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                | MutatingUseContext::AsmOutput
+                | MutatingUseContext::SetDiscriminant
+                | MutatingUseContext::Deinit,
+            ) => true,
+            PlaceContext::MutatingUse(_)
+            | PlaceContext::NonMutatingUse(NonMutatingUseContext::Move) => false,
+            // Shared reads are the whole point of two-phase borrows.
+            PlaceContext::NonMutatingUse(_) | PlaceContext::NonUse(_) => return false,
+        };
+        let len = place.projection.len().min(borrowed.projection.len());
+        if place.local != borrowed.local || place.projection[..len] != borrowed.projection[..len] {
+            return false;
+        }
+        let is_drop = context == PlaceContext::MutatingUse(MutatingUseContext::Drop);
+        !borrowed.iter_projections().skip(place.projection.len()).any(|(base, elem)| {
+            elem == ProjectionElem::Deref
+                && (shallow || is_drop && base.ty(self.body, self.tcx).ty.is_ref())
+        })
+    }
+
+    /// Checks that consecutive scope-exit drops of user variables happen in reverse declaration
+    /// order.
+    ///
+    /// MIR building allocates the locals of user variables in declaration order, and drops them
+    /// in reverse when exiting their scopes. We follow each chain of drops that are only separated
+    /// by `StorageDead`s and `goto`s, and flag a variable that is dropped before one declared
+    /// after it. User variables are recognized by their debuginfo.
+    fn check_drop_order(&self) {
+        // Optimizations may legitimately remove or move drops once borrowck has seen them.
+        if let MirPhase::Runtime(_) = self.mir_phase {
+            return;
+        }
+
+        let mut variables = BitSet::new_empty(self.body.local_decls.len());
+        for debuginfo in &self.body.var_debug_info {
+            if let VarDebugInfoContents::Place(place) = debuginfo.value
+                && debuginfo.composite.is_none()
+                && let Some(local) = place.as_local()
+            {
+                variables.insert(local);
+            }
+        }
+        // The bindings of a parameter pattern are dropped right before the parameter itself, so
+        // they are not ordered with respect to the other parameters.
+        for arg in self.body.args_iter() {
+            variables.remove(arg);
+        }
+        let user_drop = |kind: &TerminatorKind<'tcx>| match *kind {
+            TerminatorKind::Drop { place, target, replace: false, .. } => {
+                let local = place.as_local()?;
+                variables.contains(local).then_some((local, target))
+            }
+            _ => None,
+        };
+
+        for (block, data) in self.body.basic_blocks.iter_enumerated() {
+            let Some((local, mut next)) = user_drop(&data.terminator().kind) else {
+                continue;
+            };
+            // Bound the walk, as a chain of `goto`s may loop forever.
+            for _ in 0..self.body.basic_blocks.len() {
+                let next_data = &self.body.basic_blocks[next];
+                if next_data.statements.iter().any(|statement| {
+                    !matches!(statement.kind, StatementKind::StorageDead(_) | StatementKind::Nop)
+                }) {
+                    break;
+                }
+                let kind = &next_data.terminator().kind;
+                if let Some((next_local, _)) = user_drop(kind) {
+                    if next_local > local {
+                        self.fail(
+                            Location { block, statement_index: data.statements.len() },
+                            format!(
+                                "{local:?} is dropped before {next_local:?}, which is declared \
+                                 after it"
+                            ),
+                        );
+                    }
+                    break;
+                }
+                match *kind {
+                    // Drops of temporaries are not ordered with respect to user variables.
+                    TerminatorKind::Drop { target, .. } | TerminatorKind::Goto { target } => {
+                        next = target
+                    }
+                    _ => break,
+                }
+            }
+        }
+    }
+
     fn check_unwind_edge(&mut self, location: Location, unwind: UnwindAction) {
         let is_cleanup = self.body.basic_blocks[location.block].is_cleanup;
         match unwind {
@@ -319,6 +538,22 @@ fn visit_local(&mut self, local: Local, context: PlaceContext, location: Locatio
     }
 
     fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        // FIXME(JakobDegen) The validator should also check that `Retag` is not used before
+        // runtime MIR. However, this causes ICEs with generation of drop shims, which seem to fail
+        // to set their `MirPhase` correctly.
+        if !matches!(statement.kind, StatementKind::Retag(..))
+            && !statement_allowed_in_phase(&statement.kind, self.mir_phase)
+        {
+            self.fail(
+                location,
+                format!(
+                    "`{}` is not allowed in {} MIR",
+                    statement.kind.name(),
+                    self.mir_phase.name()
+                ),
+            );
+        }
+
         match &statement.kind {
             StatementKind::Assign(box (dest, rvalue)) => {
                 // FIXME(JakobDegen): Check this for all rvalues, not just this one.
@@ -333,36 +568,7 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
                     }
                 }
             }
-            StatementKind::AscribeUserType(..) => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`AscribeUserType` should have been removed after drop lowering phase",
-                    );
-                }
-            }
-            StatementKind::FakeRead(..) => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`FakeRead` should have been removed after drop lowering phase",
-                    );
-                }
-            }
-            StatementKind::SetDiscriminant { .. } => {
-                if self.mir_phase < MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(location, "`SetDiscriminant`is not allowed until deaggregation");
-                }
-            }
-            StatementKind::Deinit(..) => {
-                if self.mir_phase < MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(location, "`Deinit`is not allowed until deaggregation");
-                }
-            }
             StatementKind::Retag(kind, _) => {
-                // FIXME(JakobDegen) The validator should check that `self.mir_phase <
-                // DropsLowered`. However, this causes ICEs with generation of drop shims, which
-                // seem to fail to set their `MirPhase` correctly.
                 if matches!(kind, RetagKind::Raw | RetagKind::TwoPhase) {
                     self.fail(location, format!("explicit `{kind:?}` is forbidden"));
                 }
@@ -387,6 +593,10 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
                 }
             }
             StatementKind::StorageDead(_)
+            | StatementKind::FakeRead(..)
+            | StatementKind::AscribeUserType(..)
+            | StatementKind::SetDiscriminant { .. }
+            | StatementKind::Deinit(..)
             | StatementKind::Intrinsic(_)
             | StatementKind::Coverage(_)
             | StatementKind::ConstEvalCounter
@@ -398,6 +608,17 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
     }
 
     fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if !terminator_allowed_in_phase(&terminator.kind, self.mir_phase) {
+            self.fail(
+                location,
+                format!(
+                    "`{}` is not allowed in {} MIR",
+                    terminator.kind.name(),
+                    self.mir_phase.name()
+                ),
+            );
+        }
+
         match &terminator.kind {
             TerminatorKind::Goto { target } => {
                 self.check_edge(location, *target, EdgeKind::Normal);
@@ -489,6 +710,32 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     );
                 }
             }
+            TerminatorKind::TailCall { args, .. } => {
+                if self.body.basic_blocks[location.block].is_cleanup {
+                    self.fail(location, "`TailCall` in cleanup block");
+                }
+
+                // Like with `Call`, `Move` arguments might be passed by reference to the callee.
+                // Since the callee replaces the current frame, they additionally must not
+                // overlap with each other.
+                self.place_cache.clear();
+                let mut has_duplicates = false;
+                for arg in args {
+                    if let Operand::Move(place) = arg {
+                        has_duplicates |= !self.place_cache.insert(place.as_ref());
+                    }
+                }
+
+                if has_duplicates {
+                    self.fail(
+                        location,
+                        format!(
+                            "encountered overlapping memory in `Move` arguments to `TailCall` terminator: {:?}",
+                            terminator.kind,
+                        ),
+                    );
+                }
+            }
             TerminatorKind::Assert { target, unwind, .. } => {
                 self.check_edge(location, *target, EdgeKind::Normal);
                 self.check_unwind_edge(location, *unwind);
@@ -497,31 +744,16 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 if self.body.coroutine.is_none() {
                     self.fail(location, "`Yield` cannot appear outside coroutine bodies");
                 }
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(location, "`Yield` should have been replaced by coroutine lowering");
-                }
                 self.check_edge(location, *resume, EdgeKind::Normal);
                 if let Some(drop) = drop {
                     self.check_edge(location, *drop, EdgeKind::Normal);
                 }
             }
             TerminatorKind::FalseEdge { real_target, imaginary_target } => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`FalseEdge` should have been removed after drop elaboration",
-                    );
-                }
                 self.check_edge(location, *real_target, EdgeKind::Normal);
                 self.check_edge(location, *imaginary_target, EdgeKind::Normal);
             }
             TerminatorKind::FalseUnwind { real_target, unwind } => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`FalseUnwind` should have been removed after drop elaboration",
-                    );
-                }
                 self.check_edge(location, *real_target, EdgeKind::Normal);
                 self.check_unwind_edge(location, *unwind);
             }
@@ -530,17 +762,31 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     self.check_edge(location, *destination, EdgeKind::Normal);
                 }
                 self.check_unwind_edge(location, *unwind);
+
+                if terminator.kind.inline_asm_is_noreturn() != destination.is_none() {
+                    self.fail(
+                        location,
+                        "`InlineAsm` must have a destination if and only if it is not `noreturn`",
+                    );
+                }
+                let may_unwind = terminator.kind.inline_asm_may_unwind();
+                if !may_unwind && *unwind != UnwindAction::Unreachable {
+                    self.fail(location, "`InlineAsm` without `may_unwind` must not unwind");
+                }
+                // Inlining into a call that cannot unwind legitimately drops the unwind edge of
+                // `may_unwind` assembly, so this direction is only checked before optimizations,
+                // which start in `PostCleanup` MIR.
+                if may_unwind
+                    && *unwind == UnwindAction::Unreachable
+                    && self.mir_phase < MirPhase::Runtime(RuntimePhase::PostCleanup)
+                {
+                    self.fail(location, "`InlineAsm` with `may_unwind` must have an unwind action");
+                }
             }
             TerminatorKind::CoroutineDrop => {
                 if self.body.coroutine.is_none() {
                     self.fail(location, "`CoroutineDrop` cannot appear outside coroutine bodies");
                 }
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`CoroutineDrop` should have been replaced by coroutine lowering",
-                    );
-                }
             }
             TerminatorKind::UnwindResume => {
                 let bb = location.block;
@@ -582,6 +828,15 @@ fn visit_source_scope(&mut self, scope: SourceScope) {
     }
 }
 
+/// Collects every place mentioned by a statement or terminator together with how it is used.
+struct PlaceUses<'tcx>(Vec<(Place<'tcx>, PlaceContext)>);
+
+impl<'tcx> Visitor<'tcx> for PlaceUses<'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+        self.0.push((*place, context));
+    }
+}
+
 /// A faster version of the validation pass that only checks those things which may break when
 /// instantiating any generic parameters.
 pub fn validate_types<'tcx>(
@@ -869,7 +1124,98 @@ macro_rules! check_kinds {
             };
         }
         match rvalue {
-            Rvalue::Use(_) | Rvalue::CopyForDeref(_) | Rvalue::Aggregate(..) => {}
+            Rvalue::Use(_) | Rvalue::CopyForDeref(_) => {}
+            Rvalue::Aggregate(kind, fields) => match **kind {
+                AggregateKind::Tuple => {}
+                AggregateKind::Array(elem_ty) => {
+                    for (index, field) in fields.iter_enumerated() {
+                        let field_ty = field.ty(self.body, self.tcx);
+                        if !self.mir_assign_valid_types(field_ty, elem_ty) {
+                            self.fail(
+                                location,
+                                format!(
+                                    "array aggregate element {index:?} has type {field_ty:?}, \
+                                     but the element type is {elem_ty:?}"
+                                ),
+                            );
+                        }
+                    }
+                }
+                AggregateKind::Adt(..)
+                | AggregateKind::Closure(..)
+                | AggregateKind::Coroutine(..) => {
+                    if let Some(expected) = kind.expected_field_count(self.tcx)
+                        && fields.len() != expected
+                    {
+                        self.fail(
+                            location,
+                            format!(
+                                "{kind:?} aggregate must have {expected} fields, not {}",
+                                fields.len()
+                            ),
+                        );
+                    }
+                }
+                AggregateKind::RawPtr(pointee_ty, mutbl) => {
+                    if !matches!(self.mir_phase, MirPhase::Runtime(_)) {
+                        self.fail(location, "RawPtr should be in runtime MIR only");
+                    }
+
+                    if let [data, meta] = &fields.raw[..] {
+                        let data_ty = data.ty(self.body, self.tcx);
+                        if let ty::RawPtr(ty::TypeAndMut { ty: data_pointee, mutbl: data_mutbl }) =
+                            *data_ty.kind()
+                        {
+                            if data_mutbl != mutbl {
+                                self.fail(
+                                    location,
+                                    format!(
+                                        "cannot build a `*{}` pointer from {data_ty:?}",
+                                        mutbl.ptr_str()
+                                    ),
+                                );
+                            }
+                            if !data_pointee.is_sized(self.tcx, self.param_env) {
+                                self.fail(
+                                    location,
+                                    format!(
+                                        "RawPtr data pointer must be thin, but it is {data_ty:?}"
+                                    ),
+                                );
+                            }
+                        } else {
+                            self.fail(
+                                location,
+                                format!("RawPtr data pointer must be a raw pointer, but it is {data_ty:?}"),
+                            );
+                        }
+
+                        let meta_ty = meta.ty(self.body, self.tcx);
+                        let (expected_meta_ty, maybe_unit) =
+                            pointee_ty.ptr_metadata_ty(self.tcx, |ty| {
+                                self.tcx
+                                    .try_normalize_erasing_regions(self.param_env, ty)
+                                    .unwrap_or(ty)
+                            });
+                        if !maybe_unit && !self.mir_assign_valid_types(meta_ty, expected_meta_ty) {
+                            self.fail(
+                                location,
+                                format!(
+                                    "RawPtr metadata for {pointee_ty:?} must be {expected_meta_ty:?}, but it is {meta_ty:?}"
+                                ),
+                            );
+                        }
+                    } else {
+                        self.fail(
+                            location,
+                            format!(
+                                "raw pointer aggregate must have 2 fields, not {}",
+                                fields.len()
+                            ),
+                        );
+                    }
+                }
+            },
             Rvalue::Ref(_, BorrowKind::Fake, _) => {
                 if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
                     self.fail(
@@ -912,52 +1258,21 @@ macro_rules! check_kinds {
 
                 match op {
                     Offset => {
-                        check_kinds!(a, "Cannot offset non-pointer type {:?}", ty::RawPtr(..));
+                        if !op.accepts_type(a) {
+                            self.fail(location, format!("Cannot offset non-pointer type {a:?}"));
+                        }
                         if b != self.tcx.types.isize && b != self.tcx.types.usize {
                             self.fail(location, format!("Cannot offset by non-isize type {b:?}"));
                         }
                     }
-                    Eq | Lt | Le | Ne | Ge | Gt => {
-                        for x in [a, b] {
-                            check_kinds!(
-                                x,
-                                "Cannot {op:?} compare type {:?}",
-                                ty::Bool
-                                    | ty::Char
-                                    | ty::Int(..)
-                                    | ty::Uint(..)
-                                    | ty::Float(..)
-                                    | ty::RawPtr(..)
-                                    | ty::FnPtr(..)
-                            )
-                        }
-                    }
-                    AddUnchecked | SubUnchecked | MulUnchecked | Shl | ShlUnchecked | Shr
-                    | ShrUnchecked => {
-                        for x in [a, b] {
-                            check_kinds!(
-                                x,
-                                "Cannot {op:?} non-integer type {:?}",
-                                ty::Uint(..) | ty::Int(..)
-                            )
-                        }
-                    }
-                    BitAnd | BitOr | BitXor => {
-                        for x in [a, b] {
-                            check_kinds!(
-                                x,
-                                "Cannot perform bitwise op {op:?} on type {:?}",
-                                ty::Uint(..) | ty::Int(..) | ty::Bool
-                            )
-                        }
-                    }
-                    Add | Sub | Mul | Div | Rem => {
+                    _ => {
                         for x in [a, b] {
-                            check_kinds!(
-                                x,
-                                "Cannot perform arithmetic {op:?} on type {:?}",
-                                ty::Uint(..) | ty::Int(..) | ty::Float(..)
-                            )
+                            if !op.accepts_type(x) {
+                                self.fail(
+                                    location,
+                                    format!("Cannot perform binary op {op:?} on type {x:?}"),
+                                );
+                            }
                         }
                     }
                 }
@@ -1004,7 +1319,12 @@ macro_rules! check_kinds {
             }
             Rvalue::ShallowInitBox(operand, _) => {
                 let a = operand.ty(&self.body.local_decls, self.tcx);
-                check_kinds!(a, "Cannot shallow init type {:?}", ty::RawPtr(..));
+                if a != Ty::new_mut_ptr(self.tcx, self.tcx.types.u8) {
+                    self.fail(
+                        location,
+                        format!("Cannot shallow init type {a:?}, expected `*mut u8`"),
+                    );
+                }
             }
             Rvalue::Cast(kind, operand, target_type) => {
                 let op_ty = operand.ty(self.body, self.tcx);
@@ -1018,7 +1338,11 @@ macro_rules! check_kinds {
                     | CastKind::PointerCoercion(_) => {}
                     CastKind::IntToInt | CastKind::IntToFloat => {
                         let input_valid = op_ty.is_integral() || op_ty.is_char() || op_ty.is_bool();
-                        let target_valid = target_type.is_numeric() || target_type.is_char();
+                        let target_valid = if let CastKind::IntToInt = kind {
+                            target_type.is_integral() || target_type.is_char()
+                        } else {
+                            target_type.is_floating_point()
+                        };
                         if !input_valid || !target_valid {
                             self.fail(
                                 location,
@@ -1032,7 +1356,12 @@ macro_rules! check_kinds {
                         }
                     }
                     CastKind::FloatToFloat | CastKind::FloatToInt => {
-                        if !op_ty.is_floating_point() || !target_type.is_numeric() {
+                        let target_valid = if let CastKind::FloatToFloat = kind {
+                            target_type.is_floating_point()
+                        } else {
+                            target_type.is_integral()
+                        };
+                        if !op_ty.is_floating_point() || !target_valid {
                             self.fail(
                                 location,
                                 format!(
@@ -1121,6 +1450,11 @@ macro_rules! check_kinds {
                     }
                 }
             }
+            Rvalue::NullaryOp(NullOp::UbChecks, ty) => {
+                if !ty.is_bool() {
+                    self.fail(location, format!("`UbChecks` must have type `bool`, not {ty:?}"));
+                }
+            }
             Rvalue::Repeat(_, _)
             | Rvalue::ThreadLocalRef(_)
             | Rvalue::AddressOf(_, _)
@@ -1168,22 +1502,6 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
                     }
                 }
             }
-            StatementKind::AscribeUserType(..) => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`AscribeUserType` should have been removed after drop lowering phase",
-                    );
-                }
-            }
-            StatementKind::FakeRead(..) => {
-                if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(
-                        location,
-                        "`FakeRead` should have been removed after drop lowering phase",
-                    );
-                }
-            }
             StatementKind::Intrinsic(box NonDivergingIntrinsic::Assume(op)) => {
                 let ty = op.ty(&self.body.local_decls, self.tcx);
                 if !ty.is_bool() {
@@ -1227,10 +1545,7 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
                     self.fail(location, format!("bad arg ({op_cnt_ty:?} != usize)"))
                 }
             }
-            StatementKind::SetDiscriminant { place, .. } => {
-                if self.mir_phase < MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(location, "`SetDiscriminant`is not allowed until deaggregation");
-                }
+            StatementKind::SetDiscriminant { place, variant_index } => {
                 let pty = place.ty(&self.body.local_decls, self.tcx).ty.kind();
                 if !matches!(pty, ty::Adt(..) | ty::Coroutine(..) | ty::Alias(ty::Opaque, ..)) {
                     self.fail(
@@ -1240,22 +1555,30 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
                         ),
                     );
                 }
-            }
-            StatementKind::Deinit(..) => {
-                if self.mir_phase < MirPhase::Runtime(RuntimePhase::Initial) {
-                    self.fail(location, "`Deinit`is not allowed until deaggregation");
+                // The variants of a coroutine are only known from its layout, which is computed
+                // from the very MIR being validated, so only ADTs are checked.
+                if let ty::Adt(adt_def, _) = pty
+                    && !adt_def.variant_range().contains(variant_index)
+                {
+                    self.fail(
+                        location,
+                        format!(
+                            "`SetDiscriminant` to {variant_index:?}, but {pty:?} only has {} variants",
+                            adt_def.variants().len()
+                        ),
+                    );
                 }
             }
             StatementKind::Retag(kind, _) => {
-                // FIXME(JakobDegen) The validator should check that `self.mir_phase <
-                // DropsLowered`. However, this causes ICEs with generation of drop shims, which
-                // seem to fail to set their `MirPhase` correctly.
                 if matches!(kind, RetagKind::Raw | RetagKind::TwoPhase) {
                     self.fail(location, format!("explicit `{kind:?}` is forbidden"));
                 }
             }
             StatementKind::StorageLive(_)
             | StatementKind::StorageDead(_)
+            | StatementKind::FakeRead(..)
+            | StatementKind::AscribeUserType(..)
+            | StatementKind::Deinit(..)
             | StatementKind::Coverage(_)
             | StatementKind::ConstEvalCounter
             | StatementKind::PlaceMention(..)
@@ -1289,13 +1612,16 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     }
                 }
             }
-            TerminatorKind::Call { func, .. } => {
+            TerminatorKind::Call { func, .. } | TerminatorKind::TailCall { func, .. } => {
                 let func_ty = func.ty(&self.body.local_decls, self.tcx);
                 match func_ty.kind() {
                     ty::FnPtr(..) | ty::FnDef(..) => {}
                     _ => self.fail(
                         location,
-                        format!("encountered non-callable type {func_ty} in `Call` terminator"),
+                        format!(
+                            "encountered non-callable type {func_ty} in `{}` terminator",
+                            terminator.kind.name()
+                        ),
                     ),
                 }
             }
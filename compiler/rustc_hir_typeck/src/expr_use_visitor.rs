@@ -144,7 +144,7 @@ pub fn consume_body(&mut self, body: &hir::Body<'_>) {
 
             let param_place = self.mc.cat_rvalue(param.hir_id, param.pat.span, param_ty);
 
-            self.walk_irrefutable_pat(&param_place, param.pat);
+            self.walk_irrefutable_pat(&param_place, param.pat, FakeReadCause::ForLet);
         }
 
         self.consume_expr(body.value);
@@ -503,9 +503,11 @@ fn walk_local<F>(
                 expr_place.clone(),
                 from_ref(pat).iter()
             ));
-            self.walk_block(els)
+            self.walk_block(els);
+            self.walk_irrefutable_pat(&expr_place, pat, FakeReadCause::ForLetElse);
+        } else {
+            self.walk_irrefutable_pat(&expr_place, pat, FakeReadCause::ForLet);
         }
-        self.walk_irrefutable_pat(&expr_place, pat);
     }
 
     /// Indicates that the value of `blk` will be consumed, meaning either copied or moved
@@ -676,18 +678,20 @@ fn walk_arm(&mut self, discr_place: &PlaceWithHirId<'tcx>, arm: &hir::Arm<'_>) {
     }
 
     /// Walks a pat that occurs in isolation (i.e., top-level of fn argument or
-    /// let binding, and *not* a match arm or nested pat.)
-    fn walk_irrefutable_pat(&mut self, discr_place: &PlaceWithHirId<'tcx>, pat: &hir::Pat<'_>) {
+    /// let binding, and *not* a match arm or nested pat.) `cause` builds the cause of the fake
+    /// read of `discr_place` from the closure it is captured by, if any.
+    fn walk_irrefutable_pat(
+        &mut self,
+        discr_place: &PlaceWithHirId<'tcx>,
+        pat: &hir::Pat<'_>,
+        cause: fn(Option<LocalDefId>) -> FakeReadCause,
+    ) {
         let closure_def_id = match discr_place.place.base {
             PlaceBase::Upvar(upvar_id) => Some(upvar_id.closure_expr_id),
             _ => None,
         };
 
-        self.delegate.fake_read(
-            discr_place,
-            FakeReadCause::ForLet(closure_def_id),
-            discr_place.hir_id,
-        );
+        self.delegate.fake_read(discr_place, cause(closure_def_id), discr_place.hir_id);
         self.walk_pat(discr_place, pat, false);
     }
 
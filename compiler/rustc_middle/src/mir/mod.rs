@@ -65,6 +65,9 @@
 mod type_foldable;
 pub mod visit;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::generic_graph::graphviz_safe_def_name;
 pub use self::graphviz::write_mir_graphviz;
 pub use self::pretty::{
@@ -177,6 +180,31 @@ pub fn phase_index(&self) -> usize {
         }
     }
 
+    /// Returns the phase that follows this one, or `None` for the last phase.
+    pub fn next(self) -> Option<MirPhase> {
+        match self {
+            MirPhase::Built => Some(MirPhase::Analysis(AnalysisPhase::Initial)),
+            MirPhase::Analysis(AnalysisPhase::Initial) => {
+                Some(MirPhase::Analysis(AnalysisPhase::PostCleanup))
+            }
+            MirPhase::Analysis(AnalysisPhase::PostCleanup) => {
+                Some(MirPhase::Runtime(RuntimePhase::Initial))
+            }
+            MirPhase::Runtime(RuntimePhase::Initial) => {
+                Some(MirPhase::Runtime(RuntimePhase::PostCleanup))
+            }
+            MirPhase::Runtime(RuntimePhase::PostCleanup) => {
+                Some(MirPhase::Runtime(RuntimePhase::Optimized))
+            }
+            MirPhase::Runtime(RuntimePhase::Optimized) => None,
+        }
+    }
+
+    /// Iterates over all `MirPhase`s, in the order in which a body goes through them.
+    pub fn all() -> impl Iterator<Item = MirPhase> {
+        std::iter::successors(Some(MirPhase::Built), |phase| phase.next())
+    }
+
     /// Parses an `MirPhase` from a pair of strings. Panics if this isn't possible for any reason.
     pub fn parse(dialect: String, phase: Option<String>) -> Self {
         match &*dialect.to_ascii_lowercase() {
@@ -191,6 +219,23 @@ pub fn parse(dialect: String, phase: Option<String>) -> Self {
     }
 }
 
+/// Displays the phase by its [`name`](MirPhase::name), as used in the file names of MIR dumps.
+impl fmt::Display for MirPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a phase from its [`name`](MirPhase::name). Unlike [`MirPhase::parse`], which reads the
+/// dialect and phase of custom MIR, this only accepts the exact names and does not panic.
+impl std::str::FromStr for MirPhase {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        MirPhase::all().find(|phase| phase.name() == s).ok_or(())
+    }
+}
+
 impl AnalysisPhase {
     pub fn parse(phase: Option<String>) -> Self {
         let Some(phase) = phase else {
@@ -220,6 +265,18 @@ pub fn parse(phase: Option<String>) -> Self {
     }
 }
 
+/// The phase a body enters once its constants have been promoted, in `mir_promoted`.
+pub const CONSTS_PROMOTED: MirPhase = MirPhase::Analysis(AnalysisPhase::Initial);
+
+/// Returns whether a body in phase `from` may be changed to phase `to`.
+///
+/// Phases only ever move forward, in the order in which they are declared. Phases may be skipped,
+/// except for [`CONSTS_PROMOTED`]: a body that has not had its constants promoted must go through
+/// that phase before any later one. Shims, which have nothing to promote, still pass through it.
+pub fn mir_phase_change_is_valid(from: MirPhase, to: MirPhase) -> bool {
+    from < to && (from >= CONSTS_PROMOTED || to <= CONSTS_PROMOTED)
+}
+
 /// Where a specific `mir::Body` comes from.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[derive(HashStable, TyEncodable, TyDecodable, TypeFoldable, TypeVisitable)]
@@ -750,6 +807,14 @@ fn index(self) -> usize {
     }
 }
 
+impl Local {
+    /// Returns `true` if this is `RETURN_PLACE`, the local holding the function's return value.
+    #[inline]
+    pub fn is_return_place(self) -> bool {
+        self == RETURN_PLACE
+    }
+}
+
 /// Classifies locals into categories. See `Body::local_kind`.
 #[derive(Clone, Copy, PartialEq, Eq, Debug, HashStable)]
 pub enum LocalKind {
@@ -1,5 +1,10 @@
 /// Functionality for statements, operands, places, and things that appear in them.
-use super::{interpret::GlobalAlloc, *};
+use super::{coverage::CoverageKind, interpret::GlobalAlloc, *};
+use crate::ty::adjustment::PointerCoercion;
+use std::hash::{Hash, Hasher};
+
+#[cfg(test)]
+mod tests;
 
 ///////////////////////////////////////////////////////////////////////////
 // Statements
@@ -11,7 +16,7 @@ pub struct Statement<'tcx> {
     pub kind: StatementKind<'tcx>,
 }
 
-impl Statement<'_> {
+impl<'tcx> Statement<'tcx> {
     /// Changes a statement to a nop. This is both faster than deleting instructions and avoids
     /// invalidating statement indices in `Location`s.
     pub fn make_nop(&mut self) {
@@ -26,22 +31,162 @@ pub fn replace_nop(&mut self) -> Self {
             kind: mem::replace(&mut self.kind, StatementKind::Nop),
         }
     }
+
+    /// Replaces the kind of this statement, keeping its source info, and returns the old kind.
+    pub fn replace_kind(&mut self, kind: StatementKind<'tcx>) -> StatementKind<'tcx> {
+        mem::replace(&mut self.kind, kind)
+    }
 }
 
 impl<'tcx> StatementKind<'tcx> {
-    pub fn as_assign_mut(&mut self) -> Option<&mut (Place<'tcx>, Rvalue<'tcx>)> {
+    /// If this is an `Assign`, returns mutable references to its destination and its source.
+    pub fn as_assign_mut(&mut self) -> Option<(&mut Place<'tcx>, &mut Rvalue<'tcx>)> {
         match self {
-            StatementKind::Assign(x) => Some(x),
+            StatementKind::Assign(box (place, rvalue)) => Some((place, rvalue)),
             _ => None,
         }
     }
 
-    pub fn as_assign(&self) -> Option<&(Place<'tcx>, Rvalue<'tcx>)> {
+    /// If this is an `Assign`, returns its destination and its source.
+    pub fn as_assign(&self) -> Option<(&Place<'tcx>, &Rvalue<'tcx>)> {
         match self {
-            StatementKind::Assign(x) => Some(x),
+            StatementKind::Assign(box (place, rvalue)) => Some((place, rvalue)),
+            _ => None,
+        }
+    }
+
+    /// If this is a `SetDiscriminant`, returns the place whose discriminant is written and the
+    /// variant it is set to. This is the write counterpart of [`Rvalue::as_discriminant`].
+    pub fn as_set_discriminant(&self) -> Option<(&Place<'tcx>, VariantIdx)> {
+        match self {
+            StatementKind::SetDiscriminant { box place, variant_index } => {
+                Some((place, *variant_index))
+            }
             _ => None,
         }
     }
+
+    /// If this is a `SetDiscriminant`, returns the variant it sets.
+    pub fn set_discriminant_variant(&self) -> Option<VariantIdx> {
+        self.as_set_discriminant().map(|(_, variant_index)| variant_index)
+    }
+
+    /// If this is a `Retag`, returns the kind of the retag and the retagged place.
+    pub fn as_retag(&self) -> Option<(RetagKind, &Place<'tcx>)> {
+        match self {
+            StatementKind::Retag(kind, box place) => Some((*kind, place)),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Retag`, returns the kind of the retag and a mutable reference to the
+    /// retagged place.
+    pub fn as_retag_mut(&mut self) -> Option<(RetagKind, &mut Place<'tcx>)> {
+        match self {
+            StatementKind::Retag(kind, box place) => Some((*kind, place)),
+            _ => None,
+        }
+    }
+
+    /// Builds a call to the `copy_nonoverlapping` intrinsic. This is a thin wrapper around
+    /// `StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(..))`.
+    pub fn copy_nonoverlapping(
+        src: Operand<'tcx>,
+        dst: Operand<'tcx>,
+        count: Operand<'tcx>,
+    ) -> Self {
+        StatementKind::Intrinsic(Box::new(NonDivergingIntrinsic::CopyNonOverlapping(
+            CopyNonOverlapping { src, dst, count },
+        )))
+    }
+
+    /// If this is a call to the `copy_nonoverlapping` intrinsic, returns its operands. This is the
+    /// matching counterpart of [`StatementKind::copy_nonoverlapping`].
+    pub fn as_copy_nonoverlapping(&self) -> Option<&CopyNonOverlapping<'tcx>> {
+        match self {
+            StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
+                Some(copy)
+            }
+            _ => None,
+        }
+    }
+
+    /// If this statement copies or moves one local into another as a whole, i.e.
+    /// `_dst = copy _src` or `_dst = move _src` with no projections on either side, returns
+    /// `(dst, src)`. Such copies are lowered to a single `memcpy` for non-immediate types.
+    pub fn is_whole_local_copy(&self) -> Option<(Local, Local)> {
+        match self {
+            StatementKind::Assign(box (
+                dst,
+                Rvalue::Use(Operand::Copy(src) | Operand::Move(src)),
+            )) => Some((dst.as_local()?, src.as_local()?)),
+            _ => None,
+        }
+    }
+
+    /// Returns a rough estimate of the amount of code generated for this statement, in units of
+    /// a simple instruction, for use by heuristics such as the inliner's.
+    ///
+    /// This is a heuristic, not a real cost: statements that only matter for analyses, or that
+    /// generate no code, cost nothing, and `copy_nonoverlapping` is weighted as a `memcpy` call.
+    pub fn cost(&self) -> usize {
+        match self {
+            StatementKind::Nop
+            | StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::FakeRead(_)
+            | StatementKind::PlaceMention(_)
+            | StatementKind::AscribeUserType(..)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Intrinsic(box NonDivergingIntrinsic::Assume(_)) => 0,
+            StatementKind::Coverage(coverage) => coverage.is_counter().into(),
+            StatementKind::Assign(_)
+            | StatementKind::SetDiscriminant { .. }
+            | StatementKind::Deinit(_)
+            | StatementKind::Retag(..) => 1,
+            StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(_)) => 5,
+        }
+    }
+}
+
+impl Coverage {
+    /// Returns `true` if this statement increments a physical counter, and thus generates code.
+    pub fn is_counter(&self) -> bool {
+        matches!(self.kind, CoverageKind::CounterIncrement { .. })
+    }
+
+    /// Returns `true` if this statement only marks that an expression is used, and does not
+    /// generate any code.
+    pub fn is_zero(&self) -> bool {
+        match self.kind {
+            CoverageKind::CounterIncrement { .. } => false,
+            CoverageKind::ExpressionUsed { .. } => true,
+        }
+    }
+}
+
+/// Returns whether a statement of the given kind may appear in MIR of the given phase.
+///
+/// `FakeRead` and `AscribeUserType` only exist for the benefit of borrowck and are disallowed
+/// after drop elaboration, while `Retag`, `SetDiscriminant` and `Deinit` are only allowed
+/// starting with runtime MIR. See [`MirPhase`] and [`RuntimePhase::Initial`] for details.
+pub fn statement_allowed_in_phase(kind: &StatementKind<'_>, phase: MirPhase) -> bool {
+    match kind {
+        StatementKind::FakeRead(..) | StatementKind::AscribeUserType(..) => {
+            phase < MirPhase::Runtime(RuntimePhase::Initial)
+        }
+        StatementKind::Retag(..)
+        | StatementKind::SetDiscriminant { .. }
+        | StatementKind::Deinit(..) => phase >= MirPhase::Runtime(RuntimePhase::Initial),
+        StatementKind::Assign(..)
+        | StatementKind::StorageLive(..)
+        | StatementKind::StorageDead(..)
+        | StatementKind::PlaceMention(..)
+        | StatementKind::Coverage(..)
+        | StatementKind::Intrinsic(..)
+        | StatementKind::ConstEvalCounter
+        | StatementKind::Nop => true,
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -88,17 +233,43 @@ pub fn is_field_to(&self, f: FieldIdx) -> bool {
         matches!(*self, Self::Field(x, _) if x == f)
     }
 
+    /// If this is a `Field` projection, returns the index of the field.
+    pub fn field_index(&self) -> Option<FieldIdx> {
+        match *self {
+            Self::Field(field, _) => Some(field),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Downcast` projection, returns the name and the index of the variant.
+    pub fn as_downcast(&self) -> Option<(Option<Symbol>, VariantIdx)> {
+        match *self {
+            Self::Downcast(name, variant) => Some((name, variant)),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if this is accepted inside `VarDebugInfoContents::Place`.
+    ///
+    /// Debuginfo describes a variable's location as a fixed chain of offsets and dereferences
+    /// from the start of a local, so only projections that can be expressed that way are allowed.
     pub fn can_use_in_debuginfo(&self) -> bool {
         match self {
+            // Indexing from the start of an array is a constant offset.
             Self::ConstantIndex { from_end: false, .. }
+            // A dereference is a load of the pointer followed by further offsets.
             | Self::Deref
+            // Downcasts and fields are constant offsets into the layout.
             | Self::Downcast(_, _)
             | Self::Field(_, _) => true,
+            // Indexing from the end depends on the runtime length of the slice.
             Self::ConstantIndex { from_end: true, .. }
+            // The index is the value of another local, which is only known at runtime.
             | Self::Index(_)
+            // These only change the type of the place, which debuginfo cannot represent.
             | Self::Subtype(_)
             | Self::OpaqueCast(_)
+            // A subslice produces a new fat pointer rather than an offset into the local.
             | Self::Subslice { .. } => false,
         }
     }
@@ -150,6 +321,12 @@ pub fn local_or_deref_local(&self) -> Option<Local> {
         self.as_ref().local_or_deref_local()
     }
 
+    /// If this place is exactly a dereference of a local, like `*_X`, returns `Some(_X)`.
+    #[inline(always)]
+    pub fn is_deref_of_local(&self) -> Option<Local> {
+        self.as_ref().is_deref_of_local()
+    }
+
     /// If this place represents a local variable like `_X` with no
     /// projections, return `Some(_X)`.
     #[inline(always)]
@@ -157,6 +334,12 @@ pub fn as_local(&self) -> Option<Local> {
         self.as_ref().as_local()
     }
 
+    /// Returns `true` if this place is the return place `_0` itself, with no projections.
+    #[inline(always)]
+    pub fn is_return_place(&self) -> bool {
+        self.as_ref().is_return_place()
+    }
+
     #[inline]
     pub fn as_ref(&self) -> PlaceRef<'tcx> {
         PlaceRef { local: self.local, projection: self.projection }
@@ -185,6 +368,49 @@ pub fn project_deeper(self, more_projections: &[PlaceElem<'tcx>], tcx: TyCtxt<'t
 
         self.as_ref().project_deeper(more_projections, tcx)
     }
+
+    /// Returns the place made of the base local and the first `len` projections of this place,
+    /// e.g. `(*_1).f` for `(*_1).f[_2]` and `len == 2`.
+    ///
+    /// Panics if `len` is larger than the number of projections.
+    pub fn truncate(self, len: usize, tcx: TyCtxt<'tcx>) -> Self {
+        if len == self.projection.len() {
+            return self;
+        }
+
+        Place { local: self.local, projection: tcx.mk_place_elems(&self.projection[..len]) }
+    }
+
+    /// Replaces every local of this place, i.e. its base and the locals of its `Index`
+    /// projections, with its image under `map`.
+    pub fn remap_locals(&mut self, map: impl Fn(Local) -> Local, tcx: TyCtxt<'tcx>) {
+        self.local = map(self.local);
+        if self.projection.iter().any(|elem| matches!(elem, ProjectionElem::Index(_))) {
+            let projection: Vec<_> = self
+                .projection
+                .iter()
+                .map(|elem| match elem {
+                    ProjectionElem::Index(local) => ProjectionElem::Index(map(local)),
+                    elem => elem,
+                })
+                .collect();
+            self.projection = tcx.mk_place_elems(&projection);
+        }
+    }
+
+    /// Returns the canonical form of this place, so that places which are interchangeable for
+    /// analyses such as CSE compare equal.
+    ///
+    /// Every place is its own canonical form for now; this only provides the hook.
+    pub fn canonicalize(self, _tcx: TyCtxt<'tcx>) -> Self {
+        self
+    }
+
+    /// Returns `true` if both places have the same projections, whatever their locals.
+    pub fn projection_eq(&self, other: &Place<'tcx>) -> bool {
+        // Projections are interned, so this is a pointer comparison.
+        self.projection == other.projection
+    }
 }
 
 impl From<Local> for Place<'_> {
@@ -194,6 +420,48 @@ fn from(local: Local) -> Self {
     }
 }
 
+/// Places are ordered by their local, then lexicographically by their projections, which are
+/// compared by their kind, then by their field, index, offsets or variant.
+///
+/// This ordering is structural and only exists to make sorting places deterministic, e.g. to
+/// dedup them. It has no meaning in terms of the memory the places refer to. Like for `PlaceRef`
+/// above, it does not depend on the order of the `DefId`s and `Symbol`s in the types and names of
+/// the projections: those are only compared to break the ties between places that are identical
+/// otherwise, which never happens between the places of a single body.
+impl Ord for Place<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let key = |place: &Self| place.projection.iter().map(projection_key);
+        self.local
+            .cmp(&other.local)
+            .then_with(|| key(self).cmp(key(other)))
+            .then_with(|| self.projection.cmp(other.projection))
+    }
+}
+
+/// The parts of a projection that places are ordered by: all of it but its type or variant name.
+fn projection_key(elem: PlaceElem<'_>) -> ProjectionElem<Local, ()> {
+    match elem {
+        ProjectionElem::Deref => ProjectionElem::Deref,
+        ProjectionElem::Field(field, _) => ProjectionElem::Field(field, ()),
+        ProjectionElem::Index(local) => ProjectionElem::Index(local),
+        ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+            ProjectionElem::ConstantIndex { offset, min_length, from_end }
+        }
+        ProjectionElem::Subslice { from, to, from_end } => {
+            ProjectionElem::Subslice { from, to, from_end }
+        }
+        ProjectionElem::Downcast(_, variant) => ProjectionElem::Downcast(None, variant),
+        ProjectionElem::OpaqueCast(_) => ProjectionElem::OpaqueCast(()),
+        ProjectionElem::Subtype(_) => ProjectionElem::Subtype(()),
+    }
+}
+
+impl PartialOrd for Place<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'tcx> PlaceRef<'tcx> {
     /// Finds the innermost `Local` from this `Place`, *if* it is either a local itself or
     /// a single deref of a local.
@@ -205,6 +473,22 @@ pub fn local_or_deref_local(&self) -> Option<Local> {
         }
     }
 
+    /// If this place is exactly a dereference of a local, like `*_X`, returns `Some(_X)`.
+    ///
+    /// Unlike [`Self::local_or_deref_local`], this returns `None` for a bare local.
+    pub fn is_deref_of_local(&self) -> Option<Local> {
+        match *self {
+            PlaceRef { local, projection: [ProjectionElem::Deref] } => Some(local),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this place is the return place `_0` itself, with no projections.
+    #[inline]
+    pub fn is_return_place(&self) -> bool {
+        self.local.is_return_place() && self.projection.is_empty()
+    }
+
     /// Returns `true` if this `Place` contains a `Deref` projection.
     ///
     /// If `Place::is_indirect` returns false, the caller knows that the `Place` refers to the
@@ -236,6 +520,16 @@ pub fn as_local(&self) -> Option<Local> {
         }
     }
 
+    /// Returns the place made of the base local and the first `len` projections of this place.
+    ///
+    /// Panics if `len` is larger than the number of projections.
+    #[inline]
+    pub fn truncate(self, len: usize) -> PlaceRef<'tcx> {
+        PlaceRef { local: self.local, projection: &self.projection[..len] }
+    }
+
+    /// Splits off the last projection of this place, returning the place it applies to and the
+    /// projection itself, e.g. `(*_1, .f)` for `(*_1).f`. Returns `None` for a bare local.
     #[inline]
     pub fn last_projection(&self) -> Option<(PlaceRef<'tcx>, PlaceElem<'tcx>)> {
         if let &[ref proj_base @ .., elem] = self.projection {
@@ -282,6 +576,12 @@ pub fn project_deeper(
 
         Place { local: self.local, projection: tcx.mk_place_elems(new_projections) }
     }
+
+    /// Interns the projection of this place, turning it into an owned [`Place`]. This is the
+    /// inverse of [`Place::as_ref`].
+    pub fn to_place(self, tcx: TyCtxt<'tcx>) -> Place<'tcx> {
+        Place { local: self.local, projection: tcx.mk_place_elems(self.projection) }
+    }
 }
 
 impl From<Local> for PlaceRef<'_> {
@@ -291,6 +591,57 @@ fn from(local: Local) -> Self {
     }
 }
 
+/// Returns whether `a` and `b` may refer to overlapping memory. This is conservative: it only
+/// returns `false` if the places are known to be disjoint.
+///
+/// A place that goes through a `Deref` may point anywhere, so it may conflict with any other
+/// place. Otherwise, places based on different locals are disjoint, and places based on the same
+/// local are disjoint if they diverge at distinct fields of a type other than a union, or at
+/// distinct constant indices counted from the same end. Any other divergence, like different
+/// downcasts of an enum or a runtime `Index`, is assumed to conflict, and so is a place with a
+/// prefix of the other's projections.
+pub fn places_may_conflict<'tcx, D: ?Sized>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &D,
+    a: PlaceRef<'tcx>,
+    b: PlaceRef<'tcx>,
+) -> bool
+where
+    D: HasLocalDecls<'tcx>,
+{
+    places_may_conflict_with(a, b, |base| base.ty(local_decls, tcx).ty.is_union())
+}
+
+/// [`places_may_conflict`], with `is_union` deciding whether the fields of a place overlap.
+fn places_may_conflict_with<'tcx>(
+    a: PlaceRef<'tcx>,
+    b: PlaceRef<'tcx>,
+    is_union: impl Fn(PlaceRef<'tcx>) -> bool,
+) -> bool {
+    if a.is_indirect() || b.is_indirect() {
+        return true;
+    }
+    if a.local != b.local {
+        return false;
+    }
+    for (i, (elem_a, elem_b)) in a.projection.iter().zip(b.projection).enumerate() {
+        if elem_a == elem_b {
+            continue;
+        }
+        return match (*elem_a, *elem_b) {
+            (ProjectionElem::Field(field_a, _), ProjectionElem::Field(field_b, _)) => {
+                field_a == field_b || is_union(a.truncate(i))
+            }
+            (
+                ProjectionElem::ConstantIndex { offset: offset_a, from_end: from_end_a, .. },
+                ProjectionElem::ConstantIndex { offset: offset_b, from_end: from_end_b, .. },
+            ) => offset_a == offset_b || from_end_a != from_end_b,
+            _ => true,
+        };
+    }
+    true
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Operands
 
@@ -343,6 +694,37 @@ pub fn const_from_scalar(
         }))
     }
 
+    /// Makes a null raw pointer constant to `pointee_ty` with the given mutability, like the
+    /// result of `ptr::null()` or `ptr::null_mut()`. Assumes `user_ty` is None.
+    pub fn const_null_ptr(
+        tcx: TyCtxt<'tcx>,
+        pointee_ty: Ty<'tcx>,
+        mutbl: Mutability,
+        span: Span,
+    ) -> Operand<'tcx> {
+        let ty = Ty::new_ptr(tcx, ty::TypeAndMut { ty: pointee_ty, mutbl });
+        Operand::Constant(Box::new(ConstOperand {
+            span,
+            user_ty: None,
+            const_: Const::Val(ConstValue::Scalar(Scalar::null_ptr(&tcx)), ty),
+        }))
+    }
+
+    /// Returns `true` if this is an already evaluated raw pointer constant whose address is zero.
+    ///
+    /// Pointers into an allocation are never null, so only integer addresses are considered.
+    pub fn is_null_ptr_const(&self) -> bool {
+        self.constant().is_some_and(|constant| {
+            constant.ty().is_unsafe_ptr()
+                && constant.const_.try_to_scalar_int().is_some_and(|int| int.is_null())
+        })
+    }
+
+    /// Turns a `Move` operand into a `Copy` of the same place. Other operands are returned
+    /// unchanged.
+    ///
+    /// The caller is responsible for only using this on `Copy` types before drop elaboration, as
+    /// analysis MIR does not allow copying other types.
     pub fn to_copy(&self) -> Self {
         match *self {
             Operand::Copy(_) | Operand::Constant(_) => self.clone(),
@@ -350,6 +732,17 @@ pub fn to_copy(&self) -> Self {
         }
     }
 
+    /// Turns a `Copy` operand into a `Move` of the same place. Other operands are returned
+    /// unchanged.
+    ///
+    /// The caller is responsible for making sure the place is not used afterwards.
+    pub fn to_move(&self) -> Self {
+        match *self {
+            Operand::Move(_) | Operand::Constant(_) => self.clone(),
+            Operand::Copy(place) => Operand::Move(place),
+        }
+    }
+
     /// Returns the `Place` that is the target of this `Operand`, or `None` if this `Operand` is a
     /// constant.
     pub fn place(&self) -> Option<Place<'tcx>> {
@@ -359,6 +752,15 @@ pub fn place(&self) -> Option<Place<'tcx>> {
         }
     }
 
+    /// Applies `f` to the `Place` that is the target of this `Operand`. Does nothing if this
+    /// `Operand` is a constant.
+    pub fn map_place(&mut self, f: impl FnOnce(&mut Place<'tcx>)) {
+        match self {
+            Operand::Copy(place) | Operand::Move(place) => f(place),
+            Operand::Constant(_) => {}
+        }
+    }
+
     /// Returns the `ConstOperand` that is the target of this `Operand`, or `None` if this `Operand` is a
     /// place.
     pub fn constant(&self) -> Option<&ConstOperand<'tcx>> {
@@ -368,6 +770,53 @@ pub fn constant(&self) -> Option<&ConstOperand<'tcx>> {
         }
     }
 
+    /// Returns the `ConstOperand` of this `Operand` so that it can be changed in place, or `None`
+    /// if this `Operand` is a place.
+    pub fn as_constant_mut(&mut self) -> Option<&mut ConstOperand<'tcx>> {
+        match self {
+            Operand::Constant(x) => Some(&mut **x),
+            Operand::Copy(_) | Operand::Move(_) => None,
+        }
+    }
+
+    /// Replaces this operand with `constant`, e.g. once a pass has proven the value of the place
+    /// it reads. Constants are replaced as well.
+    pub fn promote_to_constant(&mut self, constant: ConstOperand<'tcx>) {
+        *self = Operand::Constant(Box::new(constant));
+    }
+
+    /// Returns `true` if this is an integer or floating-point constant equal to zero.
+    ///
+    /// This never evaluates constants: unevaluated constants are not considered zero. For floats,
+    /// only positive zero is considered zero, since the check is on the bits of the constant.
+    pub fn is_const_zero(&self) -> bool {
+        self.numeric_constant().is_some_and(|(int, _)| int.is_null())
+    }
+
+    /// Returns `true` if this is an integer or floating-point constant equal to one.
+    ///
+    /// This never evaluates constants: unevaluated constants are not considered one.
+    pub fn is_const_one(&self) -> bool {
+        let Some((int, ty)) = self.numeric_constant() else { return false };
+        let one = match ty.kind() {
+            ty::Float(ty::FloatTy::F32) => 1.0f32.to_bits().into(),
+            ty::Float(ty::FloatTy::F64) => 1.0f64.to_bits().into(),
+            _ => 1,
+        };
+        int.assert_bits(int.size()) == one
+    }
+
+    /// Returns the value and type of this operand if it is an already evaluated integer or
+    /// floating-point constant.
+    fn numeric_constant(&self) -> Option<(ty::ScalarInt, Ty<'tcx>)> {
+        let constant = self.constant()?;
+        let ty = constant.ty();
+        if !ty.is_integral() && !ty.is_floating_point() {
+            return None;
+        }
+        Some((constant.const_.try_to_scalar_int()?, ty))
+    }
+
     /// Gets the `ty::FnDef` from an operand if it's a constant function item.
     ///
     /// While this is unlikely in general, it's the normal case of what you'll
@@ -441,6 +890,331 @@ pub fn is_safe_to_remove(&self) -> bool {
             | Rvalue::ShallowInitBox(_, _) => true,
         }
     }
+
+    /// If this is a `Discriminant` read, returns the place whose discriminant is read. This is the
+    /// read counterpart of [`StatementKind::as_set_discriminant`].
+    pub fn as_discriminant(&self) -> Option<Place<'tcx>> {
+        match *self {
+            Rvalue::Discriminant(place) => Some(place),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Repeat`, returns the number of times its operand is repeated.
+    pub fn repeat_count(&self) -> Option<ty::Const<'tcx>> {
+        match *self {
+            Rvalue::Repeat(_, count) => Some(count),
+            _ => None,
+        }
+    }
+
+    /// If this is a `ShallowInitBox`, returns the type of the contents of the box.
+    pub fn shallow_init_box_ty(&self) -> Option<Ty<'tcx>> {
+        match *self {
+            Rvalue::ShallowInitBox(_, ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a `Repeat` whose count evaluates to zero.
+    ///
+    /// Such a repeat never stores its operand, yet the operand is still moved into it, so the
+    /// value is never dropped (see [#74836]). Passes that care about drops must handle these
+    /// separately. Returns `false` if the count cannot be evaluated.
+    ///
+    /// [#74836]: https://github.com/rust-lang/rust/issues/74836
+    pub fn is_zero_length_repeat(&self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -> bool {
+        self.repeat_count()
+            .and_then(|count| count.try_eval_target_usize(tcx, param_env))
+            .is_some_and(|count| count == 0)
+    }
+
+    /// If this is a `Ref`, replaces its region with the erased region, leaving the borrow kind and
+    /// the borrowed place untouched. This is a no-op for all other rvalues.
+    ///
+    /// Regions carry no meaning after borrowck, so this is always sound on post-borrowck MIR.
+    pub fn erase_regions_of_ref(&mut self, tcx: TyCtxt<'tcx>) {
+        if let Rvalue::Ref(region, _, _) = self {
+            *region = tcx.lifetimes.re_erased;
+        }
+    }
+
+    /// Hashes this rvalue like its `Hash` impl does, except that all regions, including those in
+    /// types, are treated as the same region. Rvalues that are equal under [`Self::value_eq`]
+    /// therefore have the same hash, so this can be used to find common subexpressions.
+    pub fn value_hash<H: Hasher>(&self, hasher: &mut H, tcx: TyCtxt<'tcx>) {
+        tcx.erase_regions(self.clone()).hash(hasher)
+    }
+
+    /// Returns whether this rvalue is equal to `other` except for the region of a `Ref`.
+    ///
+    /// Regions in types are still compared, but those are all erased after borrowck.
+    pub fn value_eq(&self, other: &Rvalue<'tcx>) -> bool {
+        match (self, other) {
+            (Rvalue::Ref(_, kind, place), Rvalue::Ref(_, other_kind, other_place)) => {
+                kind == other_kind && place == other_place
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Tries to evaluate an integer `BinaryOp` whose operands are both evaluated constants, and
+    /// returns the result as a `Use` of a constant.
+    ///
+    /// Arithmetic wraps, as the operators without overflow checks do at runtime. Nothing is
+    /// folded if an operand is not an evaluated integer constant, or if the operation is UB:
+    /// division or remainder by zero or of `MIN` by `-1`, and unchecked operations that
+    /// overflow. Shifts are only folded if the offset is smaller than the bit width. Comparisons
+    /// are not folded, as their result type differs from the type of the operands, and neither
+    /// is `Offset`.
+    pub fn try_fold_binop(&self) -> Option<Rvalue<'tcx>> {
+        let Rvalue::BinaryOp(op, box (lhs, rhs)) = self else { return None };
+        let (lhs_int, ty) = lhs.numeric_constant()?;
+        let (rhs_int, rhs_ty) = rhs.numeric_constant()?;
+        if !ty.is_integral() || !rhs_ty.is_integral() {
+            return None;
+        }
+
+        let size = lhs_int.size();
+        let is_signed = ty.is_signed();
+        let l = lhs_int.assert_bits(size);
+        let r = rhs_int.assert_bits(rhs_int.size());
+        let sext = |bits: u128| size.sign_extend(bits) as i128;
+        // Checks the exact result of an operation, interpreting the operands as unsigned or
+        // signed depending on `ty`, and only keeps it if it fits in `ty`.
+        let exact = |unsigned: Option<u128>, signed: Option<i128>| {
+            if is_signed {
+                let result = signed?;
+                (sext(size.truncate(result as u128)) == result).then_some(result as u128)
+            } else {
+                unsigned.filter(|&result| size.truncate(result) == result)
+            }
+        };
+
+        // `MIN % -1` is `0`, which fits in `ty`, but it overflows like `MIN / -1` does.
+        let is_min_by_minus_one = is_signed && sext(l) == size.signed_int_min() && sext(r) == -1;
+
+        let result = match op {
+            BinOp::Div | BinOp::Rem if is_min_by_minus_one => return None,
+            BinOp::Add => l.wrapping_add(r),
+            BinOp::Sub => l.wrapping_sub(r),
+            BinOp::Mul => l.wrapping_mul(r),
+            BinOp::AddUnchecked => exact(l.checked_add(r), sext(l).checked_add(sext(r)))?,
+            BinOp::SubUnchecked => exact(l.checked_sub(r), sext(l).checked_sub(sext(r)))?,
+            BinOp::MulUnchecked => exact(l.checked_mul(r), sext(l).checked_mul(sext(r)))?,
+            BinOp::Div => exact(l.checked_div(r), sext(l).checked_div(sext(r)))?,
+            BinOp::Rem => exact(l.checked_rem(r), sext(l).checked_rem(sext(r)))?,
+            BinOp::BitAnd => l & r,
+            BinOp::BitOr => l | r,
+            BinOp::BitXor => l ^ r,
+            BinOp::Shl | BinOp::ShlUnchecked | BinOp::Shr | BinOp::ShrUnchecked => {
+                let offset = if rhs_ty.is_signed() {
+                    u128::try_from(rhs_int.size().sign_extend(r) as i128).ok()?
+                } else {
+                    r
+                };
+                if offset >= u128::from(size.bits()) {
+                    return None;
+                }
+                match op {
+                    BinOp::Shl | BinOp::ShlUnchecked => l << offset,
+                    _ if is_signed => (sext(l) >> offset) as u128,
+                    _ => l >> offset,
+                }
+            }
+            BinOp::Eq
+            | BinOp::Lt
+            | BinOp::Le
+            | BinOp::Ne
+            | BinOp::Ge
+            | BinOp::Gt
+            | BinOp::Offset => return None,
+        };
+
+        let scalar = Scalar::from_uint(size.truncate(result), size);
+        Some(Rvalue::Use(Operand::Constant(Box::new(ConstOperand {
+            span: lhs.constant()?.span,
+            user_ty: None,
+            const_: Const::Val(ConstValue::Scalar(scalar), ty),
+        }))))
+    }
+
+    /// If this is `Not(x)` or `Neg(x)` and `x` is a place that `resolve` finds was assigned the
+    /// same unary operation of some operand `y`, returns `Use(y)`, since both operations are
+    /// their own inverse. This also holds for `Neg` of `MIN`, as `Neg` wraps at runtime.
+    ///
+    /// `resolve` returns the rvalue a place was assigned, if known. The caller must make sure that
+    /// `y` still has the same value at this rvalue as it had when that rvalue was evaluated.
+    pub fn simplify_unary<'a>(
+        &self,
+        resolve: impl Fn(&Place<'tcx>) -> Option<&'a Rvalue<'tcx>>,
+    ) -> Option<Rvalue<'tcx>>
+    where
+        'tcx: 'a,
+    {
+        let Rvalue::UnaryOp(op, operand) = self else { return None };
+        match resolve(&operand.place()?)? {
+            Rvalue::UnaryOp(inner_op, inner) if inner_op == op => Some(Rvalue::Use(inner.clone())),
+            _ => None,
+        }
+    }
+
+    /// Returns the places mentioned by this rvalue: those read by its operands, and those it
+    /// borrows, takes the address of, or reads the length or discriminant of.
+    ///
+    /// `ThreadLocalRef` and `NullaryOp` mention no place.
+    pub fn places(&self) -> impl Iterator<Item = &Place<'tcx>> {
+        let mut place = None;
+        let mut pair = [None, None];
+        let mut fields: &[Operand<'tcx>] = &[];
+        match self {
+            Rvalue::Use(operand)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::Cast(_, operand, _)
+            | Rvalue::UnaryOp(_, operand)
+            | Rvalue::ShallowInitBox(operand, _) => pair[0] = Some(operand),
+            Rvalue::BinaryOp(_, box (lhs, rhs)) | Rvalue::CheckedBinaryOp(_, box (lhs, rhs)) => {
+                pair = [Some(lhs), Some(rhs)]
+            }
+            Rvalue::Aggregate(_, operands) => fields = &operands.raw,
+            Rvalue::Ref(_, _, borrowed)
+            | Rvalue::AddressOf(_, borrowed)
+            | Rvalue::Len(borrowed)
+            | Rvalue::Discriminant(borrowed)
+            | Rvalue::CopyForDeref(borrowed) => place = Some(borrowed),
+            Rvalue::ThreadLocalRef(_) | Rvalue::NullaryOp(_, _) => {}
+        }
+        let operands = pair.into_iter().flatten().chain(fields);
+        place.into_iter().chain(operands.filter_map(|operand| match operand {
+            Operand::Copy(place) | Operand::Move(place) => Some(place),
+            Operand::Constant(_) => None,
+        }))
+    }
+
+    /// Replaces every local mentioned by this rvalue, including those of `Index` projections,
+    /// with its image under `map`. See [`Place::remap_locals`].
+    pub fn remap_locals(&mut self, map: impl Fn(Local) -> Local, tcx: TyCtxt<'tcx>) {
+        let mut remap_operand =
+            |operand: &mut Operand<'tcx>| operand.map_place(|place| place.remap_locals(&map, tcx));
+        match self {
+            Rvalue::Use(operand)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::Cast(_, operand, _)
+            | Rvalue::UnaryOp(_, operand)
+            | Rvalue::ShallowInitBox(operand, _) => remap_operand(operand),
+            Rvalue::BinaryOp(_, box (lhs, rhs)) | Rvalue::CheckedBinaryOp(_, box (lhs, rhs)) => {
+                remap_operand(lhs);
+                remap_operand(rhs);
+            }
+            Rvalue::Aggregate(_, operands) => operands.iter_mut().for_each(remap_operand),
+            Rvalue::Ref(_, _, place)
+            | Rvalue::AddressOf(_, place)
+            | Rvalue::Len(place)
+            | Rvalue::Discriminant(place)
+            | Rvalue::CopyForDeref(place) => place.remap_locals(&map, tcx),
+            Rvalue::ThreadLocalRef(_) | Rvalue::NullaryOp(_, _) => {}
+        }
+    }
+}
+
+impl<'tcx> AggregateKind<'tcx> {
+    /// Returns whether the coroutine built by this aggregate is movable, or `None` if this does
+    /// not build a coroutine.
+    pub fn movability(&self) -> Option<hir::Movability> {
+        match *self {
+            AggregateKind::Coroutine(_, _, movability) => Some(movability),
+            AggregateKind::Array(_)
+            | AggregateKind::Tuple
+            | AggregateKind::Adt(..)
+            | AggregateKind::Closure(..)
+            | AggregateKind::RawPtr(..) => None,
+        }
+    }
+
+    /// Returns the element type of the array built by this aggregate, or `None` if this does not
+    /// build an array.
+    pub fn array_elem_ty(&self) -> Option<Ty<'tcx>> {
+        match *self {
+            AggregateKind::Array(ty) => Some(ty),
+            AggregateKind::Tuple
+            | AggregateKind::Adt(..)
+            | AggregateKind::Closure(..)
+            | AggregateKind::Coroutine(..)
+            | AggregateKind::RawPtr(..) => None,
+        }
+    }
+
+    /// Returns the generic arguments of the ADT, closure or coroutine built by this aggregate.
+    pub fn args(&self) -> Option<GenericArgsRef<'tcx>> {
+        match *self {
+            AggregateKind::Adt(_, _, args, _, _)
+            | AggregateKind::Closure(_, args)
+            | AggregateKind::Coroutine(_, args, _) => Some(args),
+            AggregateKind::Array(_) | AggregateKind::Tuple | AggregateKind::RawPtr(..) => None,
+        }
+    }
+
+    /// Returns the number of operands an [`Rvalue::Aggregate`] of this kind must have, or `None`
+    /// if it is determined by the operands themselves.
+    ///
+    /// This is the number of fields of the variant for ADTs, one for unions, the number of upvars
+    /// for closures and coroutines, and two for raw pointers. Arrays and tuples have no expected
+    /// count, as their type is computed from the operands. Closures and coroutines whose upvar
+    /// types are not known also return `None`.
+    pub fn expected_field_count(&self, tcx: TyCtxt<'tcx>) -> Option<usize> {
+        match *self {
+            AggregateKind::Array(_) | AggregateKind::Tuple => None,
+            AggregateKind::Adt(_, _, _, _, Some(_)) => Some(1),
+            AggregateKind::Adt(def_id, variant_index, _, _, None) => {
+                Some(tcx.adt_def(def_id).variant(variant_index).fields.len())
+            }
+            AggregateKind::Closure(_, args) => match args.as_closure().tupled_upvars_ty().kind() {
+                ty::Tuple(upvar_tys) => Some(upvar_tys.len()),
+                _ => None,
+            },
+            AggregateKind::Coroutine(_, args, _) => {
+                match args.as_coroutine().tupled_upvars_ty().kind() {
+                    ty::Tuple(upvar_tys) => Some(upvar_tys.len()),
+                    _ => None,
+                }
+            }
+            AggregateKind::RawPtr(..) => Some(2),
+        }
+    }
+}
+
+impl CastKind {
+    /// Returns whether this cast is one that `as` performs by itself, i.e. whether it can be
+    /// written back as an `as` expression without relying on a coercion.
+    ///
+    /// `DynStar` and `Transmute` have no `as` syntax at all. The remaining pointer coercions
+    /// (reifying or unsafe-ifying a function pointer, turning a closure into a function pointer,
+    /// and unsizing) are usually implicit: they can only be spelled with `as` because `as` accepts
+    /// every coercion, so they return `false`. `*mut T` to `*const T` and array-to-pointer casts
+    /// are lowered to pointer coercions as well, but they are genuine `as` casts.
+    pub fn is_expressible_as_cast(self) -> bool {
+        match self {
+            CastKind::PointerExposeAddress
+            | CastKind::PointerFromExposedAddress
+            | CastKind::IntToInt
+            | CastKind::FloatToInt
+            | CastKind::FloatToFloat
+            | CastKind::IntToFloat
+            | CastKind::PtrToPtr
+            | CastKind::FnPtrToPtr
+            | CastKind::PointerCoercion(
+                PointerCoercion::MutToConstPointer | PointerCoercion::ArrayToPointer,
+            ) => true,
+            CastKind::PointerCoercion(
+                PointerCoercion::ReifyFnPointer
+                | PointerCoercion::UnsafeFnPointer
+                | PointerCoercion::ClosureFnPointer(_)
+                | PointerCoercion::Unsize,
+            )
+            | CastKind::DynStar
+            | CastKind::Transmute => false,
+        }
+    }
 }
 
 impl BorrowKind {
@@ -462,3 +1236,29 @@ pub fn allows_two_phase_borrow(&self) -> bool {
         }
     }
 }
+
+impl FakeReadCause {
+    /// Returns the closure whose pattern introduced this fake read, if any.
+    pub fn closure_def_id(self) -> Option<LocalDefId> {
+        match self {
+            FakeReadCause::ForMatchedPlace(closure_def_id)
+            | FakeReadCause::ForLet(closure_def_id)
+            | FakeReadCause::ForLetElse(closure_def_id) => closure_def_id,
+            FakeReadCause::ForMatchGuard
+            | FakeReadCause::ForGuardBinding
+            | FakeReadCause::ForIndex => None,
+        }
+    }
+
+    /// Returns `true` if this fake read was introduced for a `let` statement without an `else`
+    /// block. See [`Self::is_let_like`] to also include `let ... else` statements.
+    pub fn is_for_let(self) -> bool {
+        matches!(self, FakeReadCause::ForLet(_))
+    }
+
+    /// Returns `true` if this fake read was introduced for a `let` statement, with or without an
+    /// `else` block.
+    pub fn is_let_like(self) -> bool {
+        matches!(self, FakeReadCause::ForLet(_) | FakeReadCause::ForLetElse(_))
+    }
+}
@@ -0,0 +1,612 @@
+use super::*;
+use crate::mir::coverage::{CounterId, CoverageKind, ExpressionId};
+use crate::ty::{self, UserTypeAnnotationIndex};
+
+const PHASES: [MirPhase; 6] = [
+    MirPhase::Built,
+    MirPhase::Analysis(AnalysisPhase::Initial),
+    MirPhase::Analysis(AnalysisPhase::PostCleanup),
+    MirPhase::Runtime(RuntimePhase::Initial),
+    MirPhase::Runtime(RuntimePhase::PostCleanup),
+    MirPhase::Runtime(RuntimePhase::Optimized),
+];
+
+/// One statement of each kind, together with whether it is allowed in each of `PHASES`.
+fn matrix() -> Vec<(StatementKind<'static>, [bool; 6])> {
+    let place = Place::from(Local::from_u32(1));
+    let analysis_only = [true, true, true, false, false, false];
+    let runtime_only = [false, false, false, true, true, true];
+    let always = [true; 6];
+    vec![
+        (StatementKind::Assign(Box::new((place, Rvalue::Use(Operand::Copy(place))))), always),
+        (StatementKind::FakeRead(Box::new((FakeReadCause::ForLet(None), place))), analysis_only),
+        (
+            StatementKind::SetDiscriminant {
+                place: Box::new(place),
+                variant_index: VariantIdx::from_u32(0),
+            },
+            runtime_only,
+        ),
+        (StatementKind::Deinit(Box::new(place)), runtime_only),
+        (StatementKind::StorageLive(place.local), always),
+        (StatementKind::StorageDead(place.local), always),
+        (StatementKind::Retag(RetagKind::Default, Box::new(place)), runtime_only),
+        (StatementKind::PlaceMention(Box::new(place)), always),
+        (
+            StatementKind::AscribeUserType(
+                Box::new((
+                    place,
+                    UserTypeProjection {
+                        base: UserTypeAnnotationIndex::from_u32(0),
+                        projs: vec![],
+                    },
+                )),
+                ty::Variance::Invariant,
+            ),
+            analysis_only,
+        ),
+        (
+            StatementKind::Coverage(Box::new(Coverage {
+                kind: CoverageKind::CounterIncrement { id: CounterId::from_u32(0) },
+            })),
+            always,
+        ),
+        (
+            StatementKind::Intrinsic(Box::new(NonDivergingIntrinsic::Assume(Operand::Copy(place)))),
+            always,
+        ),
+        (StatementKind::ConstEvalCounter, always),
+        (StatementKind::Nop, always),
+    ]
+}
+
+/// A projection that can be built without a type context, unlike a field projection, which carries
+/// the type of the field. Field projections are tested in `tests/ui-fulldeps/mir/mir-api.rs`.
+fn constant_index(offset: u64) -> PlaceElem<'static> {
+    ProjectionElem::ConstantIndex { offset, min_length: offset + 1, from_end: false }
+}
+
+#[test]
+fn statement_phase_matrix() {
+    for (kind, expected) in matrix() {
+        for (phase, expected) in PHASES.into_iter().zip(expected) {
+            assert_eq!(
+                statement_allowed_in_phase(&kind, phase),
+                expected,
+                "`{}` in {} MIR",
+                kind.name(),
+                phase.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn operand_to_copy_and_move() {
+    let place = Place::from(Local::from_u32(1));
+    assert_eq!(Operand::Move(place).to_copy(), Operand::Copy(place));
+    assert_eq!(Operand::Copy(place).to_copy(), Operand::Copy(place));
+    assert_eq!(Operand::Copy(place).to_move(), Operand::Move(place));
+    assert_eq!(Operand::Move(place).to_move(), Operand::Move(place));
+}
+
+#[test]
+fn coverage_counters_and_expressions() {
+    let counter = Coverage { kind: CoverageKind::CounterIncrement { id: CounterId::from_u32(0) } };
+    assert!(counter.is_counter());
+    assert!(!counter.is_zero());
+
+    let expression =
+        Coverage { kind: CoverageKind::ExpressionUsed { id: ExpressionId::from_u32(0) } };
+    assert!(!expression.is_counter());
+    assert!(expression.is_zero());
+}
+
+#[test]
+fn place_local_or_deref_local() {
+    let local = Local::from_u32(1);
+
+    let place = PlaceRef { local, projection: &[] };
+    assert_eq!(place.as_local(), Some(local));
+    assert_eq!(place.local_or_deref_local(), Some(local));
+
+    let deref = PlaceRef { local, projection: &[ProjectionElem::Deref] };
+    assert_eq!(deref.as_local(), None);
+    assert_eq!(deref.local_or_deref_local(), Some(local));
+
+    let indexed = PlaceRef { local, projection: &[constant_index(0)] };
+    assert_eq!(indexed.as_local(), None);
+    assert_eq!(indexed.local_or_deref_local(), None);
+
+    let deref_indexed = PlaceRef { local, projection: &[ProjectionElem::Deref, constant_index(0)] };
+    assert_eq!(deref_indexed.as_local(), None);
+    assert_eq!(deref_indexed.local_or_deref_local(), None);
+
+    assert_eq!(Place::from(local).as_local(), Some(local));
+    assert_eq!(Place::from(local).local_or_deref_local(), Some(local));
+}
+
+#[test]
+fn place_is_deref_of_local() {
+    let local = Local::from_u32(1);
+
+    let cases: [(&[PlaceElem<'_>], bool, bool); 4] = [
+        (&[], false, false),
+        (&[ProjectionElem::Deref], true, true),
+        (&[ProjectionElem::Deref, constant_index(0)], false, true),
+        (&[constant_index(0), ProjectionElem::Deref], false, true),
+    ];
+    for (projection, is_deref_of_local, is_indirect) in cases {
+        let place = PlaceRef { local, projection };
+        assert_eq!(place.is_deref_of_local(), is_deref_of_local.then_some(local), "{place:?}");
+        assert_eq!(place.is_indirect(), is_indirect, "{place:?}");
+    }
+    assert_eq!(Place::from(local).is_deref_of_local(), None);
+}
+
+#[test]
+fn aggregate_kind_movability_and_args() {
+    let def_id = rustc_hir::def_id::CRATE_DEF_ID.to_def_id();
+    let args = ty::List::empty();
+
+    let adt = AggregateKind::Adt(def_id, VariantIdx::from_u32(0), args, None, None);
+    assert_eq!(adt.movability(), None);
+    assert_eq!(adt.args(), Some(args));
+
+    let closure = AggregateKind::Closure(def_id, args);
+    assert_eq!(closure.movability(), None);
+    assert_eq!(closure.args(), Some(args));
+
+    for movability in [hir::Movability::Static, hir::Movability::Movable] {
+        let coroutine = AggregateKind::Coroutine(def_id, args, movability);
+        assert_eq!(coroutine.movability(), Some(movability));
+        assert_eq!(coroutine.args(), Some(args));
+    }
+
+    assert_eq!(AggregateKind::Tuple.movability(), None);
+    assert_eq!(AggregateKind::Tuple.args(), None);
+}
+
+#[test]
+fn projections_usable_in_debuginfo() {
+    let cases: [(ProjectionKind, bool); 9] = [
+        (ProjectionElem::Deref, true),
+        (ProjectionElem::Field(FieldIdx::from_u32(1), ()), true),
+        (ProjectionElem::Downcast(None, VariantIdx::from_u32(1)), true),
+        (ProjectionElem::ConstantIndex { offset: 1, min_length: 2, from_end: false }, true),
+        (ProjectionElem::ConstantIndex { offset: 1, min_length: 2, from_end: true }, false),
+        (ProjectionElem::Index(()), false),
+        (ProjectionElem::Subslice { from: 1, to: 1, from_end: true }, false),
+        (ProjectionElem::Subtype(()), false),
+        (ProjectionElem::OpaqueCast(()), false),
+    ];
+    for (elem, expected) in cases {
+        assert_eq!(elem.can_use_in_debuginfo(), expected, "{elem:?}");
+    }
+}
+
+#[test]
+fn statement_costs() {
+    // Pin the cost of every statement, so that changes to the heuristic are deliberate.
+    let place = Place::from(Local::from_u32(1));
+    let operand = Operand::Copy(place);
+    let coverage = |kind| StatementKind::Coverage(Box::new(Coverage { kind }));
+    let cases = [
+        (StatementKind::Assign(Box::new((place, Rvalue::Use(operand.clone())))), 1),
+        (
+            StatementKind::SetDiscriminant {
+                place: Box::new(place),
+                variant_index: VariantIdx::from_u32(0),
+            },
+            1,
+        ),
+        (StatementKind::Deinit(Box::new(place)), 1),
+        (StatementKind::Retag(RetagKind::Default, Box::new(place)), 1),
+        // Only counters generate code.
+        (coverage(CoverageKind::CounterIncrement { id: CounterId::from_u32(0) }), 1),
+        (coverage(CoverageKind::ExpressionUsed { id: ExpressionId::from_u32(0) }), 0),
+        (StatementKind::copy_nonoverlapping(operand.clone(), operand.clone(), operand.clone()), 5),
+        (StatementKind::Intrinsic(Box::new(NonDivergingIntrinsic::Assume(operand))), 0),
+        (StatementKind::FakeRead(Box::new((FakeReadCause::ForLet(None), place))), 0),
+        (StatementKind::StorageLive(place.local), 0),
+        (StatementKind::StorageDead(place.local), 0),
+        (StatementKind::PlaceMention(Box::new(place)), 0),
+        (StatementKind::ConstEvalCounter, 0),
+        (StatementKind::Nop, 0),
+    ];
+    for (kind, cost) in cases {
+        assert_eq!(kind.cost(), cost, "{kind:?}");
+    }
+}
+
+#[test]
+fn place_ordering() {
+    use std::cmp::Ordering;
+
+    let place = |local| Place::from(Local::from_u32(local));
+    let mut places = vec![place(3), place(1), place(2), place(1)];
+    places.sort();
+    assert_eq!(places, [place(1), place(1), place(2), place(3)]);
+    places.dedup();
+    assert_eq!(places, [place(1), place(2), place(3)]);
+
+    assert_eq!(place(1).cmp(&place(1)), Ordering::Equal);
+    assert_eq!(place(1).partial_cmp(&place(2)), Some(Ordering::Less));
+}
+
+#[test]
+fn try_fold_binop_needs_constants() {
+    // Folding constants is tested in `tests/ui-fulldeps/mir/mir-api.rs`, which has a type context
+    // to build them.
+    let place = Place::from(Local::from_u32(1));
+    let add = Rvalue::BinaryOp(BinOp::Add, Box::new((Operand::Copy(place), Operand::Move(place))));
+    assert_eq!(add.try_fold_binop(), None);
+    assert_eq!(Rvalue::Use(Operand::Copy(place)).try_fold_binop(), None);
+}
+
+#[test]
+fn rvalue_value_eq() {
+    // `Ref`s need a region to be built, so only rvalues without regions are checked here, which
+    // `value_eq` compares like `==`.
+    let [a, b] = [1, 2].map(|local| Place::from(Local::from_u32(local)));
+    let address_of = |place| Rvalue::AddressOf(Mutability::Not, place);
+    assert!(address_of(a).value_eq(&address_of(a)));
+    assert!(!address_of(a).value_eq(&address_of(b)));
+    assert!(!address_of(a).value_eq(&Rvalue::Use(Operand::Copy(a))));
+}
+
+#[test]
+fn as_constant_mut_needs_constants() {
+    // Constants are tested in `tests/ui-fulldeps/mir/mir-api.rs`, which has a type context to
+    // build them.
+    let place = Place::from(Local::from_u32(1));
+    assert_eq!(Operand::Copy(place).as_constant_mut(), None);
+    assert_eq!(Operand::Move(place).as_constant_mut(), None);
+}
+
+#[test]
+fn simplify_unary() {
+    // `_1` is a `bool` and `_3` an `i32`, though the simplification does not depend on types.
+    let [b, not_b, i, neg_i] = [1, 2, 3, 4].map(|local| Place::from(Local::from_u32(local)));
+    let not = Rvalue::UnaryOp(UnOp::Not, Operand::Copy(b));
+    let neg = Rvalue::UnaryOp(UnOp::Neg, Operand::Move(i));
+    let resolve = |place: &Place<'static>| {
+        if *place == not_b {
+            Some(&not)
+        } else if *place == neg_i {
+            Some(&neg)
+        } else {
+            None
+        }
+    };
+
+    let not_not = Rvalue::UnaryOp(UnOp::Not, Operand::Copy(not_b));
+    assert_eq!(not_not.simplify_unary(resolve), Some(Rvalue::Use(Operand::Copy(b))));
+    let neg_neg = Rvalue::UnaryOp(UnOp::Neg, Operand::Move(neg_i));
+    assert_eq!(neg_neg.simplify_unary(resolve), Some(Rvalue::Use(Operand::Move(i))));
+
+    // Mixed operators, unknown places, and other rvalues are left alone.
+    assert_eq!(Rvalue::UnaryOp(UnOp::Neg, Operand::Copy(not_b)).simplify_unary(resolve), None);
+    assert_eq!(Rvalue::UnaryOp(UnOp::Not, Operand::Copy(b)).simplify_unary(resolve), None);
+    assert_eq!(Rvalue::Use(Operand::Copy(not_b)).simplify_unary(resolve), None);
+}
+
+#[test]
+fn cast_kinds_expressible_as_cast() {
+    let cases = [
+        (CastKind::PointerExposeAddress, true),
+        (CastKind::PointerFromExposedAddress, true),
+        (CastKind::PointerCoercion(PointerCoercion::ReifyFnPointer), false),
+        (CastKind::PointerCoercion(PointerCoercion::UnsafeFnPointer), false),
+        (
+            CastKind::PointerCoercion(PointerCoercion::ClosureFnPointer(hir::Unsafety::Normal)),
+            false,
+        ),
+        (CastKind::PointerCoercion(PointerCoercion::MutToConstPointer), true),
+        (CastKind::PointerCoercion(PointerCoercion::ArrayToPointer), true),
+        (CastKind::PointerCoercion(PointerCoercion::Unsize), false),
+        (CastKind::DynStar, false),
+        (CastKind::IntToInt, true),
+        (CastKind::FloatToInt, true),
+        (CastKind::FloatToFloat, true),
+        (CastKind::IntToFloat, true),
+        (CastKind::PtrToPtr, true),
+        (CastKind::FnPtrToPtr, true),
+        (CastKind::Transmute, false),
+    ];
+    for (kind, expected) in cases {
+        assert_eq!(kind.is_expressible_as_cast(), expected, "{kind:?}");
+    }
+}
+
+#[test]
+fn assign_accessors() {
+    let place = Place::from(Local::from_u32(1));
+    let mut kind = StatementKind::Assign(Box::new((place, Rvalue::Use(Operand::Copy(place)))));
+
+    let (place, rvalue) = kind.as_assign_mut().unwrap();
+    *place = Place::from(Local::from_u32(2));
+    *rvalue = Rvalue::Use(Operand::Move(Place::from(Local::from_u32(3))));
+
+    let (place, rvalue) = kind.as_assign().unwrap();
+    assert_eq!(place.as_local(), Some(Local::from_u32(2)));
+    assert_eq!(rvalue, &Rvalue::Use(Operand::Move(Place::from(Local::from_u32(3)))));
+
+    // Statements that write to a place without an rvalue are not assignments.
+    let place = Place::from(Local::from_u32(1));
+    for mut kind in [
+        StatementKind::SetDiscriminant {
+            place: Box::new(place),
+            variant_index: VariantIdx::from_u32(0),
+        },
+        StatementKind::Deinit(Box::new(place)),
+        StatementKind::StorageLive(place.local),
+        StatementKind::Nop,
+    ] {
+        assert!(kind.as_assign().is_none(), "{kind:?}");
+        assert!(kind.as_assign_mut().is_none(), "{kind:?}");
+    }
+}
+
+#[test]
+fn operand_map_place() {
+    let renumber = |place: &mut Place<'_>| place.local = Local::from_u32(place.local.as_u32() + 1);
+    let place = Place::from(Local::from_u32(1));
+    for mut operand in [Operand::Copy(place), Operand::Move(place)] {
+        operand.map_place(renumber);
+        assert_eq!(operand.place(), Some(Place::from(Local::from_u32(2))));
+    }
+}
+
+#[test]
+fn repeat_count_of_other_rvalues() {
+    let place = Place::from(Local::from_u32(1));
+    for rvalue in [
+        Rvalue::Use(Operand::Copy(place)),
+        Rvalue::Len(place),
+        Rvalue::Discriminant(place),
+        Rvalue::CopyForDeref(place),
+    ] {
+        assert!(rvalue.repeat_count().is_none());
+    }
+}
+
+#[test]
+fn place_ref_truncate() {
+    let index = ProjectionElem::Index(Local::from_u32(2));
+    let projection = [ProjectionElem::Deref, constant_index(0), index];
+    let place = PlaceRef { local: Local::from_u32(1), projection: &projection };
+
+    assert_eq!(place.truncate(0), PlaceRef::from(Local::from_u32(1)));
+    assert_eq!(place.truncate(1).projection, [ProjectionElem::Deref]);
+    assert_eq!(place.truncate(2).projection, [ProjectionElem::Deref, constant_index(0)]);
+    assert_eq!(place.truncate(3), place);
+}
+
+#[test]
+fn projection_kind_accessors() {
+    let field = FieldIdx::from_u32(1);
+    let variant = VariantIdx::from_u32(2);
+    let name = Some(rustc_span::sym::Some);
+
+    let elems: [ProjectionKind; 5] = [
+        ProjectionElem::Field(field, ()),
+        ProjectionElem::Downcast(name, variant),
+        ProjectionElem::Downcast(None, variant),
+        ProjectionElem::Deref,
+        ProjectionElem::Index(()),
+    ];
+    let expected = [
+        (Some(field), None),
+        (None, Some((name, variant))),
+        (None, Some((None, variant))),
+        (None, None),
+        (None, None),
+    ];
+    for (elem, (field_index, downcast)) in elems.iter().zip(expected) {
+        assert_eq!(elem.field_index(), field_index, "{elem:?}");
+        assert_eq!(elem.as_downcast(), downcast, "{elem:?}");
+    }
+}
+
+#[test]
+fn retag_accessors() {
+    let place = Place::from(Local::from_u32(1));
+    let mut retag = StatementKind::Retag(RetagKind::FnEntry, Box::new(place));
+    assert_eq!(retag.as_retag(), Some((RetagKind::FnEntry, &place)));
+
+    let (kind, retagged) = retag.as_retag_mut().unwrap();
+    assert_eq!(kind, RetagKind::FnEntry);
+    *retagged = Place::from(Local::from_u32(2));
+    assert_eq!(retag.as_retag(), Some((RetagKind::FnEntry, &Place::from(Local::from_u32(2)))));
+
+    // Other statements that only name a place are not retags.
+    for mut kind in [
+        StatementKind::Deinit(Box::new(place)),
+        StatementKind::PlaceMention(Box::new(place)),
+        StatementKind::StorageDead(place.local),
+    ] {
+        assert!(kind.as_retag().is_none(), "{kind:?}");
+        assert!(kind.as_retag_mut().is_none(), "{kind:?}");
+    }
+}
+
+#[test]
+fn copy_nonoverlapping_round_trip() {
+    let operand = |i| Operand::Copy(Place::from(Local::from_u32(i)));
+    let copy = StatementKind::copy_nonoverlapping(operand(1), operand(2), operand(3));
+    assert_eq!(
+        copy.as_copy_nonoverlapping(),
+        Some(&CopyNonOverlapping { src: operand(1), dst: operand(2), count: operand(3) })
+    );
+
+    // The matcher sees copies built by hand as well.
+    let nested = StatementKind::Intrinsic(Box::new(NonDivergingIntrinsic::CopyNonOverlapping(
+        CopyNonOverlapping { src: operand(4), dst: operand(5), count: operand(6) },
+    )));
+    assert_eq!(nested.as_copy_nonoverlapping().unwrap().count, operand(6));
+
+    // Other intrinsics and plain assignments are not copies.
+    let assume = StatementKind::Intrinsic(Box::new(NonDivergingIntrinsic::Assume(operand(1))));
+    assert_eq!(assume.as_copy_nonoverlapping(), None);
+    let assign =
+        StatementKind::Assign(Box::new((Place::from(Local::from_u32(2)), Rvalue::Use(operand(1)))));
+    assert_eq!(assign.as_copy_nonoverlapping(), None);
+}
+
+#[test]
+fn place_projection_eq() {
+    // Non-empty projections need to be interned, so only the empty one can be compared here.
+    let a = Place::from(Local::from_u32(1));
+    let b = Place::from(Local::from_u32(2));
+    assert!(a.projection_eq(&b));
+    assert!(a.projection_eq(&a));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn set_discriminant_variant() {
+    let place = Place::from(Local::from_u32(1));
+    let variant_index = VariantIdx::from_u32(2);
+    let set = StatementKind::SetDiscriminant { place: Box::new(place), variant_index };
+    assert_eq!(set.set_discriminant_variant(), Some(variant_index));
+
+    // Neither deinitializing nor reading the discriminant sets a variant.
+    assert_eq!(StatementKind::Deinit(Box::new(place)).set_discriminant_variant(), None);
+    let discriminant = Rvalue::Discriminant(place);
+    let read = StatementKind::Assign(Box::new((Place::from(Local::from_u32(2)), discriminant)));
+    assert_eq!(read.set_discriminant_variant(), None);
+}
+
+#[test]
+fn place_ref_last_projection() {
+    let local = Local::from_u32(1);
+    let projection = [ProjectionElem::Deref, constant_index(0)];
+    let place = PlaceRef { local, projection: &projection };
+
+    let (base, elem) = place.last_projection().unwrap();
+    assert_eq!(elem, constant_index(0));
+    assert_eq!(base, PlaceRef { local, projection: &[ProjectionElem::Deref] });
+    assert_eq!(base.last_projection(), Some((PlaceRef::from(local), ProjectionElem::Deref)));
+
+    assert_eq!(Place::from(local).as_ref(), PlaceRef::from(local));
+    assert_eq!(Place::from(local).as_ref().last_projection(), None);
+}
+
+#[test]
+fn statement_replace_kind() {
+    let source_info = SourceInfo::outermost(rustc_span::DUMMY_SP);
+    let place = Place::from(Local::from_u32(1));
+    let assign = StatementKind::Assign(Box::new((place, Rvalue::Use(Operand::Copy(place)))));
+    let mut statement = Statement { source_info, kind: assign.clone() };
+
+    assert_eq!(statement.replace_kind(StatementKind::Nop), assign);
+    assert_eq!(statement.kind, StatementKind::Nop);
+    assert_eq!(statement.source_info, source_info);
+}
+
+#[test]
+fn return_place() {
+    assert!(RETURN_PLACE.is_return_place());
+    assert!(Place::return_place().is_return_place());
+    assert!(Place::from(RETURN_PLACE).as_ref().is_return_place());
+
+    assert!(!PlaceRef { local: RETURN_PLACE, projection: &[constant_index(0)] }.is_return_place());
+
+    let local = Local::from_u32(1);
+    assert!(!local.is_return_place());
+    assert!(!Place::from(local).is_return_place());
+}
+
+#[test]
+fn fake_read_causes() {
+    let closure = Some(rustc_hir::def_id::CRATE_DEF_ID);
+    // (cause, is_for_let, is_let_like)
+    let cases = [
+        (FakeReadCause::ForMatchGuard, false, false),
+        (FakeReadCause::ForMatchedPlace(closure), false, false),
+        (FakeReadCause::ForGuardBinding, false, false),
+        (FakeReadCause::ForLet(closure), true, true),
+        (FakeReadCause::ForLetElse(closure), false, true),
+        (FakeReadCause::ForIndex, false, false),
+    ];
+    for (cause, is_for_let, is_let_like) in cases {
+        assert_eq!(cause.is_for_let(), is_for_let, "{cause:?}");
+        assert_eq!(cause.is_let_like(), is_let_like, "{cause:?}");
+    }
+
+    assert_eq!(FakeReadCause::ForLetElse(closure).closure_def_id(), closure);
+    assert_eq!(FakeReadCause::ForLetElse(None).closure_def_id(), None);
+}
+
+#[test]
+fn fake_read_cause_closure_def_id() {
+    let closure = rustc_hir::def_id::CRATE_DEF_ID;
+    for payload in [Some(closure), None] {
+        // (cause, closure_def_id, is_for_let)
+        for (cause, expected, is_for_let) in [
+            (FakeReadCause::ForMatchedPlace(payload), payload, false),
+            (FakeReadCause::ForLet(payload), payload, true),
+            (FakeReadCause::ForLetElse(payload), payload, false),
+            (FakeReadCause::ForMatchGuard, None, false),
+            (FakeReadCause::ForGuardBinding, None, false),
+            (FakeReadCause::ForIndex, None, false),
+        ] {
+            assert_eq!(cause.closure_def_id(), expected, "{cause:?}");
+            assert_eq!(cause.is_for_let(), is_for_let, "{cause:?}");
+        }
+    }
+}
+
+#[test]
+fn places_may_conflict() {
+    let [_1, _2] = [1, 2].map(Local::from_u32);
+    // The projections are locals, as the closures below tie all the places to a single lifetime.
+    let [x, y] = [[constant_index(0)], [constant_index(1)]];
+    let last = [ProjectionElem::ConstantIndex { offset: 1, min_length: 2, from_end: true }];
+    let index = [ProjectionElem::Index(_2)];
+    let place = |local, projection| PlaceRef { local, projection };
+    let conflict = |a, b| places_may_conflict_with(a, b, |_| false);
+    assert!(!conflict(place(_1, &x), place(_1, &y)));
+    assert!(conflict(place(_1, &x), place(_1, &x)));
+    assert!(!conflict(PlaceRef::from(_1), PlaceRef::from(_2)));
+    assert!(conflict(place(_1, &[ProjectionElem::Deref]), PlaceRef::from(_2)));
+    // A place conflicts with the places it contains.
+    assert!(conflict(PlaceRef::from(_1), place(_1, &x)));
+    // Counting from different ends may reach the same element.
+    assert!(conflict(place(_1, &x), place(_1, &last)));
+    assert!(conflict(place(_1, &index), place(_1, &x)));
+}
+
+#[test]
+fn whole_local_copies() {
+    // Copies of a field or through a reference need a type context to be built, and are tested in
+    // `tests/ui-fulldeps/mir/mir-api.rs`.
+    let [dst, src] = [1, 2].map(|local| Place::from(Local::from_u32(local)));
+    let assign = |rvalue| StatementKind::Assign(Box::new((dst, rvalue)));
+    let whole = Some((dst.local, src.local));
+    assert_eq!(assign(Rvalue::Use(Operand::Copy(src))).is_whole_local_copy(), whole);
+    assert_eq!(assign(Rvalue::Use(Operand::Move(src))).is_whole_local_copy(), whole);
+    assert_eq!(assign(Rvalue::CopyForDeref(src)).is_whole_local_copy(), None);
+    assert_eq!(StatementKind::StorageLive(dst.local).is_whole_local_copy(), None);
+}
+
+#[test]
+fn place_operands_are_not_const_zero_or_one() {
+    // Constants need a type context to be built, and are tested in
+    // `tests/ui-fulldeps/mir/mir-api.rs`.
+    let place = Place::from(Local::from_u32(1));
+    for operand in [Operand::Copy(place), Operand::Move(place)] {
+        assert!(!operand.is_const_zero());
+        assert!(!operand.is_const_one());
+    }
+}
+
+#[test]
+fn place_operands_are_not_null_ptr_consts() {
+    // Constants need a type context to be built, and are tested in
+    // `tests/ui-fulldeps/mir/mir-api.rs`.
+    let place = Place::from(Local::from_u32(1));
+    assert!(!Operand::Copy(place).is_null_ptr_const());
+    assert!(!Operand::Move(place).is_null_ptr_const());
+}
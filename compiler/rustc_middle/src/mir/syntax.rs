@@ -41,7 +41,7 @@
 /// that it has the same semantic meaning. In this sense, phase changes can only add additional
 /// restrictions on what MIR is well-formed.
 ///
-/// When adding phases, remember to update [`MirPhase::phase_index`].
+/// When adding phases, remember to update [`MirPhase::phase_index`] and [`MirPhase::next`].
 #[derive(Copy, Clone, TyEncodable, TyDecodable, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[derive(HashStable)]
 pub enum MirPhase {
@@ -505,6 +505,14 @@ pub enum FakeReadCause {
     /// Otherwise, the value of the optional DefId will be None.
     ForLet(Option<LocalDefId>),
 
+    /// Like `ForLet`, but for the pattern of a `let ... else { ... }` statement. The scrutinee is
+    /// also read to test it against the pattern, so diagnostics that explain a plain `let`
+    /// binding do not apply here.
+    ///
+    /// The optional DefId is the closure whose pattern introduced this fake read, as in
+    /// `ForLet`.
+    ForLetElse(Option<LocalDefId>),
+
     /// If we have an index expression like
     ///
     /// (*x)[1][{ x = y; 4}]
@@ -515,6 +523,14 @@ pub enum FakeReadCause {
     ForIndex,
 }
 
+/// A coverage statement, which marks the point in control flow represented by a counter or an
+/// expression.
+///
+/// It carries no code region: those are stored once per body in the `mappings` of its
+/// [`FunctionCoverageInfo`](super::coverage::FunctionCoverageInfo), which refer to counters and
+/// expressions by id. Passes that merge or remove blocks can therefore move or drop coverage
+/// statements without touching any region; see [`CoverageKind`] for what happens to the mappings
+/// of a statement that does not survive.
 #[derive(Clone, Debug, PartialEq, TyEncodable, TyDecodable, Hash, HashStable)]
 #[derive(TypeFoldable, TypeVisitable)]
 pub struct Coverage {
@@ -688,6 +704,38 @@ pub enum TerminatorKind<'tcx> {
         fn_span: Span,
     },
 
+    /// Tail call.
+    ///
+    /// Roughly speaking this is a chimera of [`Call`] and [`Return`], with some caveats.
+    /// Semantically tail calls consist of two actions:
+    /// - pop of the current stack frame
+    /// - a call to the `func`, with the return address of the **current** caller
+    ///   - so that a `return` inside `func` returns to the caller of the caller
+    ///     of the function that is currently being executed
+    ///
+    /// Note that unlike [`Call`] this is missing
+    /// - `destination` (because it's always the return place)
+    /// - `target` (because it's always taken from the current stack frame)
+    /// - `unwind` (because it's always taken from the current stack frame)
+    ///
+    /// Since the current frame is replaced, this terminator has no successors and is not
+    /// permitted in cleanup blocks.
+    ///
+    /// [`Call`]: TerminatorKind::Call
+    /// [`Return`]: TerminatorKind::Return
+    TailCall {
+        /// The function that’s being called.
+        func: Operand<'tcx>,
+        /// Arguments the function is called with.
+        /// These are owned by the callee, which is free to modify them.
+        /// This allows the memory occupied by "by-value" arguments to be
+        /// reused across function calls without duplicating the contents.
+        args: Vec<Operand<'tcx>>,
+        /// This `Span` is the span of the function, without the dot and receiver
+        /// e.g. `foo(a, b)` in `x.foo(a, b)`
+        fn_span: Span,
+    },
+
     /// Evaluates the operand, which must have type `bool`. If it is not equal to `expected`,
     /// initiates a panic. Initiating a panic corresponds to a `Call` terminator with some
     /// unspecified constant as the function to call, all the operands stored in the `AssertMessage`
@@ -813,6 +861,7 @@ pub const fn name(&self) -> &'static str {
             TerminatorKind::Unreachable => "Unreachable",
             TerminatorKind::Drop { .. } => "Drop",
             TerminatorKind::Call { .. } => "Call",
+            TerminatorKind::TailCall { .. } => "TailCall",
             TerminatorKind::Assert { .. } => "Assert",
             TerminatorKind::Yield { .. } => "Yield",
             TerminatorKind::CoroutineDrop => "CoroutineDrop",
@@ -1139,6 +1188,9 @@ pub enum Operand<'tcx> {
     Move(Place<'tcx>),
 
     /// Constants are already semantically values, and remain unchanged.
+    ///
+    /// Each constant operand owns its box. The type and the value inside it are interned, so
+    /// identical constants share everything but the small `ConstOperand` itself.
     Constant(Box<ConstOperand<'tcx>>),
 }
 
@@ -1314,9 +1366,15 @@ pub enum CastKind {
     PointerCoercion(PointerCoercion),
     /// Cast into a dyn* object.
     DynStar,
+    /// A cast between integers, where `bool`, `char` and the discriminants of fieldless enums also
+    /// count as integers. Truncates or extends the value as needed.
     IntToInt,
+    /// A cast from a float to an integer. This is saturating: out-of-range values are clamped to
+    /// the bounds of the integer type and `NaN` becomes `0`.
     FloatToInt,
+    /// A cast between float types, rounding to the nearest representable value if needed.
     FloatToFloat,
+    /// A cast from an integer to a float, rounding to the nearest representable value if needed.
     IntToFloat,
     PtrToPtr,
     FnPtrToPtr,
@@ -1345,6 +1403,21 @@ pub enum AggregateKind<'tcx> {
 
     Closure(DefId, GenericArgsRef<'tcx>),
     Coroutine(DefId, GenericArgsRef<'tcx>, hir::Movability),
+
+    /// Construct a raw pointer from the data pointer and metadata.
+    ///
+    /// The `Ty` here is the type of the *pointee*, not the pointer itself. The `Mutability`
+    /// indicates whether this produces a `*const` or `*mut`.
+    ///
+    /// The [`Rvalue::Aggregate`] operands for this must be
+    ///
+    /// 0. A raw pointer of matching mutability to any sized type
+    /// 1. A value of the appropriate metadata type: `usize` for slices and `str`, `()` for sized
+    ///    pointees
+    ///
+    /// *Both* operands must always be included, even the unit value if this is creating a thin
+    /// pointer. This is only allowed in runtime MIR.
+    RawPtr(Ty<'tcx>, Mutability),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, Hash, HashStable)]
@@ -1355,6 +1428,12 @@ pub enum NullOp<'tcx> {
     AlignOf,
     /// Returns the offset of a field
     OffsetOf(&'tcx List<(VariantIdx, FieldIdx)>),
+    /// Returns whether checks for undefined behavior are enabled, which is the value of
+    /// `cfg!(debug_assertions)` in the crate that is being codegened (rather than the crate that
+    /// this MIR comes from), so that library code can be checked in debug builds of its users.
+    ///
+    /// The result is a `bool`. The type operand carries no meaning and must be `bool` as well.
+    UbChecks,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1433,6 +1512,24 @@ pub enum BinOp {
     Offset,
 }
 
+/// How an arithmetic [`BinOp`] behaves when its exact result does not fit in the type of its
+/// left-hand side. See [`BinOp::arith_kind`] and [`BinOp::with_arith_kind`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ArithKind {
+    /// Overflow is UB, like for `AddUnchecked`. For shifts, an offset that is negative or at
+    /// least the bit width of the left-hand side is UB.
+    Unchecked,
+    /// The result wraps around, like for `Add`. This is how the operators without a suffix behave
+    /// even with `-C overflow-checks`, which instead inserts an `Assert` on the result of a
+    /// `CheckedBinaryOp`. For shifts, the offset is masked to the bit width.
+    Wrapping,
+    /// The result is clamped to the range of the type.
+    ///
+    /// No `BinOp` has this behavior: `saturating_add` and `saturating_sub` are still lowered to
+    /// calls to the intrinsics of the same name.
+    Saturating,
+}
+
 // Some nodes are used a lot. Make sure they don't unintentionally get bigger.
 #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
 mod size_asserts {
@@ -120,6 +120,18 @@ pub fn projection_ty_core<V, T>(
     }
 }
 
+impl<'tcx> ProjectionElem<Local, Ty<'tcx>> {
+    /// Returns the type of a place of type `base_ty` after applying this projection, following
+    /// the rules in the docs of [`Place`]: the pointee for `Deref`, the element type for `Index`
+    /// and `ConstantIndex`, a slice or an array of the same element type for `Subslice`, the
+    /// stored type for `Field`, `OpaqueCast` and `Subtype`, and `base_ty` itself for `Downcast`.
+    ///
+    /// This is [`PlaceTy::projection_ty`] for a single projection.
+    pub fn projected_ty(&self, base_ty: Ty<'tcx>, tcx: TyCtxt<'tcx>) -> Ty<'tcx> {
+        PlaceTy::from_ty(base_ty).projection_ty(tcx, *self).ty
+    }
+}
+
 impl<'tcx> Place<'tcx> {
     pub fn ty_from<D: ?Sized>(
         local: Local,
@@ -143,6 +155,18 @@ pub fn ty<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> PlaceTy<'tcx>
     {
         Place::ty_from(self.local, self.projection, local_decls, tcx)
     }
+
+    /// Returns `true` if this place dereferences a `Box`. `ElaborateBoxDerefs` turns these into
+    /// dereferences of the raw pointer inside the box, so they are not allowed from
+    /// `RuntimePhase::PostCleanup` on.
+    pub fn has_box_deref<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> bool
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        self.iter_projections().any(|(base, elem)| {
+            elem == ProjectionElem::Deref && base.ty(local_decls, tcx).ty.is_box()
+        })
+    }
 }
 
 impl<'tcx> PlaceRef<'tcx> {
@@ -159,6 +183,15 @@ pub enum RvalueInitializationState {
     Deep,
 }
 
+/// What the place of an `Rvalue::Len` computes the length of.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LenKind {
+    /// An array, whose length is known at compile time.
+    Array(u64),
+    /// A slice, whose length is only known at runtime.
+    Slice,
+}
+
 impl<'tcx> Rvalue<'tcx> {
     pub fn ty<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> Ty<'tcx>
     where
@@ -193,19 +226,23 @@ pub fn ty<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> Ty<'tcx>
             }
             Rvalue::UnaryOp(UnOp::Not | UnOp::Neg, ref operand) => operand.ty(local_decls, tcx),
             Rvalue::Discriminant(ref place) => place.ty(local_decls, tcx).ty.discriminant_ty(tcx),
+            Rvalue::NullaryOp(NullOp::UbChecks, _) => tcx.types.bool,
             Rvalue::NullaryOp(NullOp::SizeOf | NullOp::AlignOf | NullOp::OffsetOf(..), _) => {
                 tcx.types.usize
             }
             Rvalue::Aggregate(ref ak, ref ops) => match **ak {
                 AggregateKind::Array(ty) => Ty::new_array(tcx, ty, ops.len() as u64),
                 AggregateKind::Tuple => {
-                    Ty::new_tup_from_iter(tcx, ops.iter().map(|op| op.ty(local_decls, tcx)))
+                    Ty::new_tup_from_iter(tcx, Operand::tys(&ops.raw, local_decls, tcx))
                 }
                 AggregateKind::Adt(did, _, args, _, _) => tcx.type_of(did).instantiate(tcx, args),
                 AggregateKind::Closure(did, args) => Ty::new_closure(tcx, did, args),
                 AggregateKind::Coroutine(did, args, movability) => {
                     Ty::new_coroutine(tcx, did, args, movability)
                 }
+                AggregateKind::RawPtr(pointee_ty, mutbl) => {
+                    Ty::new_ptr(tcx, ty::TypeAndMut { ty: pointee_ty, mutbl })
+                }
             },
             Rvalue::ShallowInitBox(_, ty) => Ty::new_box(tcx, ty),
             Rvalue::CopyForDeref(ref place) => place.ty(local_decls, tcx).ty,
@@ -221,6 +258,50 @@ pub fn initialization_state(&self) -> RvalueInitializationState {
             _ => RvalueInitializationState::Deep,
         }
     }
+
+    /// If this is an `Rvalue::Len`, returns whether it takes the length of an array or of a
+    /// slice. Returns `None` for other rvalues and for arrays whose length is not yet known.
+    pub fn len_kind<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> Option<LenKind>
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        let Rvalue::Len(place) = self else { return None };
+        match *place.ty(local_decls, tcx).ty.kind() {
+            ty::Array(_, len) => len.try_to_target_usize(tcx).map(LenKind::Array),
+            ty::Slice(_) => Some(LenKind::Slice),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a numeric `Cast` to the type its operand already has, e.g.
+    /// `x as i32` for `x: i32`, which can be replaced by a `Use` of the operand.
+    ///
+    /// Only `IntToInt` and `FloatToFloat` casts are considered. Other casts that do not change
+    /// the type, such as pointer casts that only change lifetimes, are left alone.
+    pub fn is_identity_cast<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> bool
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        match *self {
+            Rvalue::Cast(CastKind::IntToInt | CastKind::FloatToFloat, ref operand, ty) => {
+                operand.ty(local_decls, tcx) == ty
+            }
+            _ => false,
+        }
+    }
+
+    /// If this is a `CheckedBinaryOp`, returns the type of its result, i.e. the tuple `(T, bool)`
+    /// of the type `T` of the operation and the overflow flag.
+    pub fn checked_binop_result_ty<D: ?Sized>(
+        &self,
+        local_decls: &D,
+        tcx: TyCtxt<'tcx>,
+    ) -> Option<Ty<'tcx>>
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        matches!(self, Rvalue::CheckedBinaryOp(..)).then(|| self.ty(local_decls, tcx))
+    }
 }
 
 impl<'tcx> Operand<'tcx> {
@@ -233,6 +314,31 @@ pub fn ty<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> Ty<'tcx>
             Operand::Constant(c) => c.const_.ty(),
         }
     }
+
+    /// Returns the types of `operands`, e.g. of the arguments of a call, in order.
+    pub fn tys<'a, D: ?Sized>(
+        operands: &'a [Operand<'tcx>],
+        local_decls: &'a D,
+        tcx: TyCtxt<'tcx>,
+    ) -> impl Iterator<Item = Ty<'tcx>> + 'a
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        operands.iter().map(move |operand| operand.ty(local_decls, tcx))
+    }
+}
+
+impl<'tcx> CopyNonOverlapping<'tcx> {
+    /// Returns the type of the elements being copied, or `None` if `src` and `dst` are not
+    /// pointers (or references, or boxes) to the same type.
+    pub fn pointee_ty<D: ?Sized>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> Option<Ty<'tcx>>
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        let src = self.src.ty(local_decls, tcx).builtin_deref(true)?.ty;
+        let dst = self.dst.ty(local_decls, tcx).builtin_deref(true)?.ty;
+        (src == dst).then_some(src)
+    }
 }
 
 impl<'tcx> BinOp {
@@ -266,6 +372,40 @@ pub fn ty(&self, tcx: TyCtxt<'tcx>, lhs_ty: Ty<'tcx>, rhs_ty: Ty<'tcx>) -> Ty<'t
             }
         }
     }
+
+    /// Returns whether this operator is defined for operands of type `ty`.
+    ///
+    /// For `Offset`, this only describes the left-hand side, as the offset itself must be an
+    /// `isize` or `usize`. For all other operators, it applies to both operands; whether the two
+    /// operand types have to match is not checked here.
+    pub fn accepts_type(self, ty: Ty<'tcx>) -> bool {
+        match self {
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem => {
+                matches!(ty.kind(), ty::Int(..) | ty::Uint(..) | ty::Float(..))
+            }
+            BinOp::AddUnchecked
+            | BinOp::SubUnchecked
+            | BinOp::MulUnchecked
+            | BinOp::Shl
+            | BinOp::ShlUnchecked
+            | BinOp::Shr
+            | BinOp::ShrUnchecked => matches!(ty.kind(), ty::Int(..) | ty::Uint(..)),
+            BinOp::BitXor | BinOp::BitAnd | BinOp::BitOr => {
+                matches!(ty.kind(), ty::Int(..) | ty::Uint(..) | ty::Bool)
+            }
+            BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Ne | BinOp::Ge | BinOp::Gt => matches!(
+                ty.kind(),
+                ty::Bool
+                    | ty::Char
+                    | ty::Int(..)
+                    | ty::Uint(..)
+                    | ty::Float(..)
+                    | ty::RawPtr(..)
+                    | ty::FnPtr(..)
+            ),
+            BinOp::Offset => matches!(ty.kind(), ty::RawPtr(..)),
+        }
+    }
 }
 
 impl BorrowKind {
@@ -282,8 +422,75 @@ pub fn to_mutbl_lossy(self) -> hir::Mutability {
 }
 
 impl BinOp {
-    pub fn to_hir_binop(self) -> hir::BinOpKind {
+    /// Returns the operator that gives the same result when the operands are swapped, i.e. `op2`
+    /// such that `a op b == b op2 a`, or `None` if there is no such operator.
+    pub fn swap_operands(self) -> Option<BinOp> {
         match self {
+            BinOp::Add
+            | BinOp::AddUnchecked
+            | BinOp::Mul
+            | BinOp::MulUnchecked
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::BitXor
+            | BinOp::Eq
+            | BinOp::Ne => Some(self),
+            BinOp::Lt => Some(BinOp::Gt),
+            BinOp::Le => Some(BinOp::Ge),
+            BinOp::Gt => Some(BinOp::Lt),
+            BinOp::Ge => Some(BinOp::Le),
+            BinOp::Sub
+            | BinOp::SubUnchecked
+            | BinOp::Div
+            | BinOp::Rem
+            | BinOp::Shl
+            | BinOp::ShlUnchecked
+            | BinOp::Shr
+            | BinOp::ShrUnchecked
+            | BinOp::Offset => None,
+        }
+    }
+
+    /// Evaluates this operator on two integers given as bit patterns, if it is a comparison, and
+    /// returns `None` for any other operator.
+    ///
+    /// If `signed` is set, the operands are compared as `i128`, so the bit patterns of signed
+    /// values must have been sign-extended to 128 bits first, e.g. with [`Size::sign_extend`].
+    ///
+    /// [`Size::sign_extend`]: rustc_target::abi::Size::sign_extend
+    pub fn eval_bool(self, a: u128, b: u128, signed: bool) -> Option<bool> {
+        let ordering = if signed { (a as i128).cmp(&(b as i128)) } else { a.cmp(&b) };
+        Some(match self {
+            BinOp::Eq => ordering.is_eq(),
+            BinOp::Ne => ordering.is_ne(),
+            BinOp::Lt => ordering.is_lt(),
+            BinOp::Le => ordering.is_le(),
+            BinOp::Gt => ordering.is_gt(),
+            BinOp::Ge => ordering.is_ge(),
+            BinOp::Add
+            | BinOp::AddUnchecked
+            | BinOp::Sub
+            | BinOp::SubUnchecked
+            | BinOp::Mul
+            | BinOp::MulUnchecked
+            | BinOp::Div
+            | BinOp::Rem
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Shl
+            | BinOp::ShlUnchecked
+            | BinOp::Shr
+            | BinOp::ShrUnchecked
+            | BinOp::Offset => return None,
+        })
+    }
+
+    /// Returns the HIR operator that this operator is lowered from, e.g. for rendering it the way
+    /// the source did. Returns `None` for `Offset` and the unchecked operators, which do not
+    /// correspond to any operator in the surface language.
+    pub fn to_hir_binop(self) -> Option<hir::BinOpKind> {
+        Some(match self {
             BinOp::Add => hir::BinOpKind::Add,
             BinOp::Sub => hir::BinOpKind::Sub,
             BinOp::Mul => hir::BinOpKind::Mul,
@@ -305,9 +512,77 @@ pub fn to_hir_binop(self) -> hir::BinOpKind {
             | BinOp::MulUnchecked
             | BinOp::ShlUnchecked
             | BinOp::ShrUnchecked
-            | BinOp::Offset => {
-                unreachable!()
+            | BinOp::Offset => return None,
+        })
+    }
+
+    /// Returns how this operator behaves on overflow, or `None` if it is not an arithmetic
+    /// operator that can overflow. Division and remainder return `None` as well, as their only
+    /// overflow, `MIN / -1`, is always UB.
+    pub fn arith_kind(self) -> Option<ArithKind> {
+        match self {
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Shl | BinOp::Shr => {
+                Some(ArithKind::Wrapping)
             }
+            BinOp::AddUnchecked
+            | BinOp::SubUnchecked
+            | BinOp::MulUnchecked
+            | BinOp::ShlUnchecked
+            | BinOp::ShrUnchecked => Some(ArithKind::Unchecked),
+            BinOp::Div
+            | BinOp::Rem
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Eq
+            | BinOp::Lt
+            | BinOp::Le
+            | BinOp::Ne
+            | BinOp::Ge
+            | BinOp::Gt
+            | BinOp::Offset => None,
+        }
+    }
+
+    /// Returns the operator that computes the same operation as this one, but with the given
+    /// behavior on overflow, or `None` if there is no such operator. This is the inverse of
+    /// [`Self::arith_kind`].
+    ///
+    /// There are no saturating operators, so this returns `None` for `ArithKind::Saturating`.
+    pub fn with_arith_kind(self, kind: ArithKind) -> Option<BinOp> {
+        let (wrapping, unchecked) = match self {
+            BinOp::Add | BinOp::AddUnchecked => (BinOp::Add, BinOp::AddUnchecked),
+            BinOp::Sub | BinOp::SubUnchecked => (BinOp::Sub, BinOp::SubUnchecked),
+            BinOp::Mul | BinOp::MulUnchecked => (BinOp::Mul, BinOp::MulUnchecked),
+            BinOp::Shl | BinOp::ShlUnchecked => (BinOp::Shl, BinOp::ShlUnchecked),
+            BinOp::Shr | BinOp::ShrUnchecked => (BinOp::Shr, BinOp::ShrUnchecked),
+            BinOp::Div
+            | BinOp::Rem
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Eq
+            | BinOp::Lt
+            | BinOp::Le
+            | BinOp::Ne
+            | BinOp::Ge
+            | BinOp::Gt
+            | BinOp::Offset => return None,
+        };
+        match kind {
+            ArithKind::Wrapping => Some(wrapping),
+            ArithKind::Unchecked => Some(unchecked),
+            ArithKind::Saturating => None,
+        }
+    }
+}
+
+impl UnOp {
+    /// Returns the HIR operator that this operator is lowered from.
+    pub fn to_hir_unop(self) -> hir::UnOp {
+        match self {
+            UnOp::Not => hir::UnOp::Not,
+            UnOp::Neg => hir::UnOp::Neg,
         }
     }
 }
@@ -1,4 +1,5 @@
 /// Functionality for terminators and helper types that appear in terminators.
+use rustc_ast::InlineAsmOptions;
 use rustc_hir::LangItem;
 use smallvec::SmallVec;
 
@@ -9,6 +10,9 @@
 
 use super::*;
 
+#[cfg(test)]
+mod tests;
+
 impl SwitchTargets {
     /// Creates switch targets from an iterator of values and target blocks.
     ///
@@ -61,12 +65,30 @@ pub fn all_targets_mut(&mut self) -> &mut [BasicBlock] {
         &mut self.targets
     }
 
+    /// Returns a slice with all considered values (not including the fallback).
+    pub fn all_values(&self) -> &[u128] {
+        &self.values
+    }
+
     /// Finds the `BasicBlock` to which this `SwitchInt` will branch given the
     /// specific value. This cannot fail, as it'll return the `otherwise`
     /// branch if there's not a specific match for the value.
     pub fn target_for_value(&self, value: u128) -> BasicBlock {
         self.iter().find_map(|(v, t)| (v == value).then_some(t)).unwrap_or_else(|| self.otherwise())
     }
+
+    /// Returns the fraction of the values between the smallest and the largest listed value that
+    /// have an explicit target, which is `1.0` for a contiguous range of values. Dense switches are
+    /// good candidates for jump tables, while sparse ones are better lowered to a tree of branches.
+    ///
+    /// The values are compared as their unsigned bit patterns. Returns `0.0` if there are no values.
+    pub fn target_density(&self) -> f64 {
+        let (Some(&min), Some(&max)) = (self.values.iter().min(), self.values.iter().max()) else {
+            return 0.0;
+        };
+        // Computing the span as a float avoids overflowing for `0..=u128::MAX`.
+        self.values.len() as f64 / ((max - min) as f64 + 1.0)
+    }
 }
 
 pub struct SwitchTargetsIter<'a> {
@@ -129,6 +151,54 @@ pub fn is_optional_overflow_check(&self) -> bool {
         matches!(self, OverflowNeg(..) | Overflow(Add | Sub | Mul | Shl | Shr, ..))
     }
 
+    /// Returns true if this assertion guards an arithmetic operation against overflow or a
+    /// zero divisor.
+    ///
+    /// Only the subset for which [`Self::is_optional_overflow_check`] holds may be removed when
+    /// `-C overflow-checks` is off: division by zero and `MIN / -1` are UB in every mode. Bounds
+    /// checks are never optional.
+    pub fn is_overflow_check(&self) -> bool {
+        use AssertKind::*;
+        match self {
+            Overflow(..) | OverflowNeg(_) | DivisionByZero(_) | RemainderByZero(_) => true,
+            BoundsCheck { .. }
+            | ResumedAfterReturn(_)
+            | ResumedAfterPanic(_)
+            | MisalignedPointerDereference { .. } => false,
+        }
+    }
+
+    /// Returns the operands embedded in this assertion message, in the order in which they are
+    /// passed to the panic machinery.
+    pub fn operands(&self) -> SmallVec<[&O; 2]> {
+        use AssertKind::*;
+        match self {
+            BoundsCheck { len, index } => smallvec![len, index],
+            Overflow(_, l, r) => smallvec![l, r],
+            MisalignedPointerDereference { required, found } => smallvec![required, found],
+            OverflowNeg(op) | DivisionByZero(op) | RemainderByZero(op) => smallvec![op],
+            ResumedAfterReturn(_) | ResumedAfterPanic(_) => SmallVec::new(),
+        }
+    }
+
+    /// Applies `f` to each operand embedded in this assertion message, in the same order as
+    /// [`Self::operands`], and keeps everything else as it is.
+    pub fn map<P>(self, mut f: impl FnMut(O) -> P) -> AssertKind<P> {
+        use AssertKind::*;
+        match self {
+            BoundsCheck { len, index } => BoundsCheck { len: f(len), index: f(index) },
+            Overflow(op, l, r) => Overflow(op, f(l), f(r)),
+            OverflowNeg(op) => OverflowNeg(f(op)),
+            DivisionByZero(op) => DivisionByZero(f(op)),
+            RemainderByZero(op) => RemainderByZero(f(op)),
+            ResumedAfterReturn(coroutine_kind) => ResumedAfterReturn(coroutine_kind),
+            ResumedAfterPanic(coroutine_kind) => ResumedAfterPanic(coroutine_kind),
+            MisalignedPointerDereference { required, found } => {
+                MisalignedPointerDereference { required: f(required), found: f(found) }
+            }
+        }
+    }
+
     /// Get the message that is printed at runtime when this assertion fails.
     ///
     /// The caller is expected to handle `BoundsCheck` and `MisalignedPointerDereference` by
@@ -285,7 +355,7 @@ macro_rules! add {
                 add!("val", format!("{val:#?}"));
             }
             Overflow(binop, left, right) => {
-                add!("op", binop.to_hir_binop().as_str());
+                add!("op", binop.to_hir_binop().unwrap().as_str());
                 add!("left", format!("{left:#?}"));
                 add!("right", format!("{right:#?}"));
             }
@@ -358,6 +428,7 @@ pub fn successors(&self) -> Successors<'_> {
             | CoroutineDrop
             | Return
             | Unreachable
+            | TailCall { .. }
             | Call { target: None, unwind: _, .. }
             | InlineAsm { destination: None, unwind: _, .. } => {
                 None.into_iter().chain((&[]).into_iter().copied())
@@ -400,6 +471,7 @@ pub fn successors_mut(&mut self) -> SuccessorsMut<'_> {
             | CoroutineDrop
             | Return
             | Unreachable
+            | TailCall { .. }
             | Call { target: None, unwind: _, .. }
             | InlineAsm { destination: None, unwind: _, .. } => None.into_iter().chain(&mut []),
             SwitchInt { ref mut targets, .. } => None.into_iter().chain(&mut targets.targets),
@@ -409,12 +481,26 @@ pub fn successors_mut(&mut self) -> SuccessorsMut<'_> {
         }
     }
 
+    /// Returns a copy of this terminator with every successor edge, as yielded by
+    /// [`Self::successors_mut`], replaced by `map` of it. This terminator is left unchanged.
+    pub fn cloned_with_blocks(&self, map: impl Fn(BasicBlock) -> BasicBlock) -> Self {
+        let mut kind = self.clone();
+        for target in kind.successors_mut() {
+            *target = map(*target);
+        }
+        kind
+    }
+
+    /// Returns the `unwind` field of the terminators that can unwind: `Call`, `Assert`, `Drop`,
+    /// `FalseUnwind` and `InlineAsm`. Every other terminator returns `None`, including `Yield`,
+    /// whose `drop` edge is taken when the coroutine is dropped rather than when it unwinds.
     pub fn unwind(&self) -> Option<&UnwindAction> {
         match *self {
             TerminatorKind::Goto { .. }
             | TerminatorKind::UnwindResume
             | TerminatorKind::UnwindTerminate(_)
             | TerminatorKind::Return
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Unreachable
             | TerminatorKind::CoroutineDrop
             | TerminatorKind::Yield { .. }
@@ -428,12 +514,14 @@ pub fn unwind(&self) -> Option<&UnwindAction> {
         }
     }
 
+    /// Mutable version of [`TerminatorKind::unwind`].
     pub fn unwind_mut(&mut self) -> Option<&mut UnwindAction> {
         match *self {
             TerminatorKind::Goto { .. }
             | TerminatorKind::UnwindResume
             | TerminatorKind::UnwindTerminate(_)
             | TerminatorKind::Return
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Unreachable
             | TerminatorKind::CoroutineDrop
             | TerminatorKind::Yield { .. }
@@ -454,12 +542,234 @@ pub fn as_switch(&self) -> Option<(&Operand<'tcx>, &SwitchTargets)> {
         }
     }
 
+    /// If this is a `SwitchInt`, returns its values together with the blocks they jump to, not
+    /// including the `otherwise` target. See [`SwitchTargets::iter`].
+    pub fn switch_targets_iter(&self) -> Option<impl Iterator<Item = (u128, BasicBlock)> + '_> {
+        self.as_switch().map(|(_, targets)| targets.iter())
+    }
+
+    /// If this is a `SwitchInt`, returns the block it jumps to when none of the values match.
+    pub fn switch_otherwise(&self) -> Option<BasicBlock> {
+        self.as_switch().map(|(_, targets)| targets.otherwise())
+    }
+
     pub fn as_goto(&self) -> Option<BasicBlock> {
         match self {
             TerminatorKind::Goto { target } => Some(*target),
             _ => None,
         }
     }
+
+    /// If this is a `Call`, returns its arguments. This does not include `TailCall`s.
+    pub fn call_args(&self) -> Option<&[Operand<'tcx>]> {
+        match self {
+            TerminatorKind::Call { args, .. } => Some(args),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Call`, returns its arguments, so they can be rewritten, added or removed.
+    pub fn call_args_mut(&mut self) -> Option<&mut Vec<Operand<'tcx>>> {
+        match self {
+            TerminatorKind::Call { args, .. } => Some(args),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Drop`, returns the dropped place. This includes the drops that precede an
+    /// assignment to the place, i.e. those with `replace` set.
+    pub fn drop_place(&self) -> Option<&Place<'tcx>> {
+        match self {
+            TerminatorKind::Drop { place, .. } => Some(place),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Drop`, returns the block it continues to after the drop, whether or not
+    /// `replace` is set.
+    pub fn drop_target(&self) -> Option<BasicBlock> {
+        match self {
+            TerminatorKind::Drop { target, .. } => Some(*target),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Yield`, returns the block it continues to when the coroutine is resumed,
+    /// and the place that the resume argument is written to.
+    pub fn yield_resume(&self) -> Option<(BasicBlock, &Place<'tcx>)> {
+        match self {
+            TerminatorKind::Yield { resume, resume_arg, .. } => Some((*resume, resume_arg)),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Yield`, returns the block it continues to when the coroutine is dropped
+    /// while suspended. Returns `None` for a `Yield` without such a block too.
+    pub fn yield_drop(&self) -> Option<BasicBlock> {
+        match self {
+            TerminatorKind::Yield { drop, .. } => *drop,
+            _ => None,
+        }
+    }
+
+    /// If this is a `SwitchInt`, removes the arms whose value `reachable` rejects. If only the
+    /// `otherwise` target is left, the terminator becomes a `Goto` to it. Other terminators are
+    /// left unchanged.
+    ///
+    /// Values are only ever removed, never added or changed, so the remaining values are still
+    /// distinct, as [`SwitchTargets`] requires.
+    pub fn prune_switch_targets(&mut self, reachable: impl Fn(u128) -> bool) {
+        let TerminatorKind::SwitchInt { targets, .. } = self else { return };
+        let pruned = SwitchTargets::new(
+            targets.iter().filter(|&(value, _)| reachable(value)),
+            targets.otherwise(),
+        );
+        if pruned.all_values().is_empty() {
+            *self = TerminatorKind::Goto { target: pruned.otherwise() };
+        } else {
+            *targets = pruned;
+        }
+    }
+
+    /// If this is an `Assert`, flips its `expected` value, so that it now succeeds for the other
+    /// value of `cond`. Other terminators are left unchanged.
+    ///
+    /// The targets and the message are kept as they are. This inverts the meaning of the assertion
+    /// on its own, so callers must also negate `cond` (or otherwise keep the message and the
+    /// success target consistent) unless that is the point.
+    pub fn negate_assert(&mut self) {
+        if let TerminatorKind::Assert { expected, .. } = self {
+            *expected = !*expected;
+        }
+    }
+
+    /// Returns the items referenced by the operands of an `InlineAsm` terminator, or nothing for
+    /// any other terminator.
+    ///
+    /// `sym` operands naming a static contribute their def-id directly. `sym` operands naming a
+    /// function contribute the `FnDef` of their constant, and `const` operands contribute the
+    /// definition of their constant if it has not been evaluated yet. Anything else (evaluated
+    /// constants in particular) would need a `TyCtxt` to resolve and is skipped.
+    pub fn inline_asm_referenced_def_ids(&self) -> SmallVec<[DefId; 4]> {
+        let TerminatorKind::InlineAsm { operands, .. } = self else {
+            return SmallVec::new();
+        };
+        operands
+            .iter()
+            .filter_map(|op| match op {
+                InlineAsmOperand::SymStatic { def_id } => Some(*def_id),
+                InlineAsmOperand::SymFn { value } => match *value.ty().kind() {
+                    ty::FnDef(def_id, _) => Some(def_id),
+                    _ => None,
+                },
+                InlineAsmOperand::Const { value } => match value.const_ {
+                    Const::Unevaluated(uv, _) => Some(uv.def),
+                    Const::Ty(c) => match c.kind() {
+                        ty::ConstKind::Unevaluated(uv) => Some(uv.def),
+                        _ => None,
+                    },
+                    Const::Val(..) => None,
+                },
+                InlineAsmOperand::In { .. }
+                | InlineAsmOperand::Out { .. }
+                | InlineAsmOperand::InOut { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if this is an `InlineAsm` terminator with the `may_unwind` option.
+    pub fn inline_asm_may_unwind(&self) -> bool {
+        matches!(
+            self,
+            TerminatorKind::InlineAsm { options, .. } if options.contains(InlineAsmOptions::MAY_UNWIND)
+        )
+    }
+
+    /// Returns `true` if this is an `InlineAsm` terminator with the `noreturn` option.
+    pub fn inline_asm_is_noreturn(&self) -> bool {
+        matches!(
+            self,
+            TerminatorKind::InlineAsm { options, .. } if options.contains(InlineAsmOptions::NORETURN)
+        )
+    }
+
+    /// Returns `true` if this terminator never continues to a normal successor in the current
+    /// body, i.e. if it returns, unwinds, aborts, tail calls or is unreachable.
+    ///
+    /// Whether a `Call` or an `InlineAsm` diverges depends on its optional target: it diverges
+    /// exactly when `target`, respectively `destination`, is `None`. Unwind edges are not normal
+    /// successors, so they do not affect the result.
+    pub fn is_diverging(&self) -> bool {
+        match self {
+            TerminatorKind::Return
+            | TerminatorKind::TailCall { .. }
+            | TerminatorKind::UnwindResume
+            | TerminatorKind::UnwindTerminate(_)
+            | TerminatorKind::Unreachable
+            | TerminatorKind::CoroutineDrop => true,
+            TerminatorKind::Call { target, .. } => target.is_none(),
+            TerminatorKind::InlineAsm { destination, .. } => destination.is_none(),
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::SwitchInt { .. }
+            | TerminatorKind::Drop { .. }
+            | TerminatorKind::Assert { .. }
+            | TerminatorKind::Yield { .. }
+            | TerminatorKind::FalseEdge { .. }
+            | TerminatorKind::FalseUnwind { .. } => false,
+        }
+    }
+
+    /// Returns a rough estimate of the amount of code generated for this terminator, in the same
+    /// units as [`StatementKind::cost`], for use by heuristics such as the inliner's.
+    ///
+    /// This is a heuristic, not a real cost: jumps and returns are cheap, call-like terminators
+    /// are weighted as a call, a `SwitchInt` grows with its number of targets, and inline
+    /// assembly is assumed to be expensive since its contents are opaque.
+    pub fn cost(&self) -> usize {
+        match self {
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::UnwindResume
+            | TerminatorKind::UnwindTerminate(_)
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable
+            | TerminatorKind::CoroutineDrop
+            | TerminatorKind::FalseEdge { .. }
+            | TerminatorKind::FalseUnwind { .. } => 1,
+            TerminatorKind::Assert { .. } => 2,
+            TerminatorKind::SwitchInt { targets, .. } => 1 + targets.all_targets().len(),
+            TerminatorKind::Drop { .. }
+            | TerminatorKind::Call { .. }
+            | TerminatorKind::TailCall { .. }
+            | TerminatorKind::Yield { .. } => 5,
+            TerminatorKind::InlineAsm { .. } => 20,
+        }
+    }
+}
+
+/// Returns whether a terminator of the given kind may appear in MIR of the given phase.
+///
+/// `FalseEdge` and `FalseUnwind` only exist for the benefit of borrowck and are disallowed after
+/// drop elaboration, and `Yield` and `CoroutineDrop` are disallowed once coroutines are lowered,
+/// which is also when runtime MIR starts. See [`MirPhase`] and [`RuntimePhase::Initial`] for
+/// details.
+pub fn terminator_allowed_in_phase(kind: &TerminatorKind<'_>, phase: MirPhase) -> bool {
+    match kind {
+        TerminatorKind::FalseEdge { .. }
+        | TerminatorKind::FalseUnwind { .. }
+        | TerminatorKind::Yield { .. }
+        | TerminatorKind::CoroutineDrop => phase < MirPhase::Runtime(RuntimePhase::Initial),
+        TerminatorKind::Goto { .. }
+        | TerminatorKind::SwitchInt { .. }
+        | TerminatorKind::UnwindResume
+        | TerminatorKind::UnwindTerminate(_)
+        | TerminatorKind::Return
+        | TerminatorKind::Unreachable
+        | TerminatorKind::Drop { .. }
+        | TerminatorKind::Call { .. }
+        | TerminatorKind::TailCall { .. }
+        | TerminatorKind::Assert { .. }
+        | TerminatorKind::InlineAsm { .. } => true,
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -495,18 +805,58 @@ pub fn for_each(&self, mut f: impl FnMut(Place<'tcx>)) {
         match *self {
             Self::Call(place) | Self::Yield(place) => f(place),
             Self::InlineAsm(operands) => {
-                for op in operands {
-                    match *op {
-                        InlineAsmOperand::Out { place: Some(place), .. }
-                        | InlineAsmOperand::InOut { out_place: Some(place), .. } => f(place),
-                        _ => {}
-                    }
+                for place in operands.iter().flat_map(InlineAsmOperand::places) {
+                    f(*place);
                 }
             }
         }
     }
 }
 
+impl<'tcx> InlineAsmOperand<'tcx> {
+    /// Returns the place written by this operand, i.e. the output place of an `out` or `inout`
+    /// operand, unless the output is discarded.
+    pub fn places(&self) -> impl Iterator<Item = &Place<'tcx>> {
+        match self {
+            InlineAsmOperand::Out { place, .. }
+            | InlineAsmOperand::InOut { out_place: place, .. } => place.as_ref(),
+            InlineAsmOperand::In { .. }
+            | InlineAsmOperand::Const { .. }
+            | InlineAsmOperand::SymFn { .. }
+            | InlineAsmOperand::SymStatic { .. } => None,
+        }
+        .into_iter()
+    }
+
+    /// Returns the value read by this operand, i.e. the input of an `in` or `inout` operand.
+    pub fn operands(&self) -> impl Iterator<Item = &Operand<'tcx>> {
+        match self {
+            InlineAsmOperand::In { value, .. }
+            | InlineAsmOperand::InOut { in_value: value, .. } => Some(value),
+            InlineAsmOperand::Out { .. }
+            | InlineAsmOperand::Const { .. }
+            | InlineAsmOperand::SymFn { .. }
+            | InlineAsmOperand::SymStatic { .. } => None,
+        }
+        .into_iter()
+    }
+
+    /// Returns the constant of a `const` or `sym` function operand.
+    ///
+    /// These are kept apart from [`InlineAsmOperand::operands`], as they are stored as a
+    /// [`ConstOperand`] rather than an [`Operand`].
+    pub fn constants(&self) -> impl Iterator<Item = &ConstOperand<'tcx>> {
+        match self {
+            InlineAsmOperand::Const { value } | InlineAsmOperand::SymFn { value } => Some(&**value),
+            InlineAsmOperand::In { .. }
+            | InlineAsmOperand::Out { .. }
+            | InlineAsmOperand::InOut { .. }
+            | InlineAsmOperand::SymStatic { .. } => None,
+        }
+        .into_iter()
+    }
+}
+
 impl<'tcx> Terminator<'tcx> {
     pub fn edges(&self) -> TerminatorEdges<'_, 'tcx> {
         self.kind.edges()
@@ -517,9 +867,12 @@ impl<'tcx> TerminatorKind<'tcx> {
     pub fn edges(&self) -> TerminatorEdges<'_, 'tcx> {
         use TerminatorKind::*;
         match *self {
-            Return | UnwindResume | UnwindTerminate(_) | CoroutineDrop | Unreachable => {
-                TerminatorEdges::None
-            }
+            Return
+            | TailCall { .. }
+            | UnwindResume
+            | UnwindTerminate(_)
+            | CoroutineDrop
+            | Unreachable => TerminatorEdges::None,
 
             Goto { target } => TerminatorEdges::Single(target),
 
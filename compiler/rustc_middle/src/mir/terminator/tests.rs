@@ -0,0 +1,614 @@
+use super::*;
+use rustc_ast::InlineAsmOptions;
+use rustc_span::DUMMY_SP;
+
+const PHASES: [MirPhase; 6] = [
+    MirPhase::Built,
+    MirPhase::Analysis(AnalysisPhase::Initial),
+    MirPhase::Analysis(AnalysisPhase::PostCleanup),
+    MirPhase::Runtime(RuntimePhase::Initial),
+    MirPhase::Runtime(RuntimePhase::PostCleanup),
+    MirPhase::Runtime(RuntimePhase::Optimized),
+];
+
+/// One terminator of each kind, together with whether it is allowed in each of `PHASES`.
+fn matrix() -> Vec<(TerminatorKind<'static>, [bool; 6])> {
+    let place = Place::from(Local::from_u32(1));
+    let operand = Operand::Copy(place);
+    let target = BasicBlock::from_u32(1);
+    let unwind = UnwindAction::Continue;
+    let analysis_only = [true, true, true, false, false, false];
+    let always = [true; 6];
+    vec![
+        (TerminatorKind::Goto { target }, always),
+        (
+            TerminatorKind::SwitchInt {
+                discr: operand.clone(),
+                targets: SwitchTargets::static_if(0, target, target),
+            },
+            always,
+        ),
+        (TerminatorKind::UnwindResume, always),
+        (TerminatorKind::UnwindTerminate(UnwindTerminateReason::Abi), always),
+        (TerminatorKind::Return, always),
+        (TerminatorKind::Unreachable, always),
+        (TerminatorKind::Drop { place, target, unwind, replace: false }, always),
+        (
+            TerminatorKind::Call {
+                func: operand.clone(),
+                args: vec![],
+                destination: place,
+                target: Some(target),
+                unwind,
+                call_source: CallSource::Normal,
+                fn_span: DUMMY_SP,
+            },
+            always,
+        ),
+        (
+            TerminatorKind::TailCall { func: operand.clone(), args: vec![], fn_span: DUMMY_SP },
+            always,
+        ),
+        (
+            TerminatorKind::Assert {
+                cond: operand.clone(),
+                expected: true,
+                msg: Box::new(AssertKind::OverflowNeg(operand.clone())),
+                target,
+                unwind,
+            },
+            always,
+        ),
+        (
+            TerminatorKind::Yield {
+                value: operand.clone(),
+                resume: target,
+                resume_arg: place,
+                drop: None,
+            },
+            analysis_only,
+        ),
+        (TerminatorKind::CoroutineDrop, analysis_only),
+        (
+            TerminatorKind::FalseEdge { real_target: target, imaginary_target: target },
+            analysis_only,
+        ),
+        (TerminatorKind::FalseUnwind { real_target: target, unwind }, analysis_only),
+        (
+            TerminatorKind::InlineAsm {
+                template: &[],
+                operands: vec![],
+                options: InlineAsmOptions::empty(),
+                line_spans: &[],
+                destination: Some(target),
+                unwind,
+            },
+            always,
+        ),
+    ]
+}
+
+#[test]
+fn terminator_phase_matrix() {
+    for (kind, expected) in matrix() {
+        for (phase, expected) in PHASES.into_iter().zip(expected) {
+            assert_eq!(
+                terminator_allowed_in_phase(&kind, phase),
+                expected,
+                "`{}` in {} MIR",
+                kind.name(),
+                phase.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn tail_call_has_no_successors() {
+    let func = Operand::Copy(Place::from(Local::from_u32(1)));
+    let args = vec![Operand::Move(Place::from(Local::from_u32(2)))];
+    let mut kind = TerminatorKind::TailCall { func, args, fn_span: DUMMY_SP };
+
+    assert_eq!(kind.successors().count(), 0);
+    assert_eq!(kind.successors_mut().count(), 0);
+    assert!(matches!(kind.edges(), TerminatorEdges::None));
+    assert_eq!(kind.unwind(), None);
+    assert_eq!(kind.unwind_mut(), None);
+    assert!(kind.fmt_successor_labels().is_empty());
+    assert_eq!(format!("{kind:?}"), "tailcall _1(move _2)");
+}
+
+#[test]
+fn switch_target_density() {
+    let bb = BasicBlock::from_u32;
+    let dense = SwitchTargets::new((3..7).map(|value| (value, bb(value as u32))), bb(0));
+    assert_eq!(dense.target_density(), 1.0);
+
+    let sparse = SwitchTargets::new([(0, bb(1)), (10, bb(2)), (99, bb(3))].into_iter(), bb(0));
+    assert_eq!(sparse.target_density(), 0.03);
+
+    let single = SwitchTargets::static_if(u128::MAX, bb(1), bb(0));
+    assert_eq!(single.target_density(), 1.0);
+
+    let empty = SwitchTargets::new(std::iter::empty(), bb(0));
+    assert_eq!(empty.target_density(), 0.0);
+}
+
+#[test]
+fn inline_asm_operand_places_and_operands() {
+    use rustc_hir::def_id::CRATE_DEF_ID;
+    use rustc_target::asm::{InlineAsmRegClass, InlineAsmRegOrRegClass, X86InlineAsmRegClass};
+
+    let reg = InlineAsmRegOrRegClass::RegClass(InlineAsmRegClass::X86(X86InlineAsmRegClass::reg));
+    let input = Operand::Copy(Place::from(Local::from_u32(1)));
+    let output = Place::from(Local::from_u32(2));
+    let operands = [
+        InlineAsmOperand::In { reg, value: input.clone() },
+        InlineAsmOperand::Out { reg, late: false, place: Some(output) },
+        InlineAsmOperand::Out { reg, late: true, place: None },
+        InlineAsmOperand::InOut {
+            reg,
+            late: false,
+            in_value: input.clone(),
+            out_place: Some(output),
+        },
+        InlineAsmOperand::InOut { reg, late: true, in_value: input.clone(), out_place: None },
+        InlineAsmOperand::SymStatic { def_id: CRATE_DEF_ID.to_def_id() },
+    ];
+
+    let places: Vec<_> = operands.iter().map(|op| op.places().collect::<Vec<_>>()).collect();
+    assert_eq!(places, [vec![], vec![&output], vec![], vec![&output], vec![], vec![]]);
+
+    let values: Vec<_> = operands.iter().map(|op| op.operands().collect::<Vec<_>>()).collect();
+    assert_eq!(values, [vec![&input], vec![], vec![], vec![&input], vec![&input], vec![]]);
+
+    assert!(operands.iter().all(|op| op.constants().next().is_none()));
+}
+
+#[test]
+fn terminator_costs() {
+    // Pin the cost of every terminator, so that changes to the heuristic are deliberate.
+    let place = Place::from(Local::from_u32(1));
+    let operand = Operand::Copy(place);
+    let target = BasicBlock::from_u32(1);
+    let unwind = UnwindAction::Continue;
+    let cases = [
+        (TerminatorKind::Goto { target }, 1),
+        (TerminatorKind::UnwindResume, 1),
+        (TerminatorKind::UnwindTerminate(UnwindTerminateReason::Abi), 1),
+        (TerminatorKind::Return, 1),
+        (TerminatorKind::Unreachable, 1),
+        (TerminatorKind::CoroutineDrop, 1),
+        (TerminatorKind::FalseEdge { real_target: target, imaginary_target: target }, 1),
+        (TerminatorKind::FalseUnwind { real_target: target, unwind }, 1),
+        (
+            TerminatorKind::Assert {
+                cond: operand.clone(),
+                expected: true,
+                msg: Box::new(AssertKind::OverflowNeg(operand.clone())),
+                target,
+                unwind,
+            },
+            2,
+        ),
+        (TerminatorKind::Drop { place, target, unwind, replace: false }, 5),
+        (
+            TerminatorKind::Call {
+                func: operand.clone(),
+                args: vec![],
+                destination: place,
+                target: Some(target),
+                unwind,
+                call_source: CallSource::Normal,
+                fn_span: DUMMY_SP,
+            },
+            5,
+        ),
+        (TerminatorKind::TailCall { func: operand.clone(), args: vec![], fn_span: DUMMY_SP }, 5),
+        (
+            TerminatorKind::Yield { value: operand, resume: target, resume_arg: place, drop: None },
+            5,
+        ),
+        (
+            TerminatorKind::InlineAsm {
+                template: &[],
+                operands: vec![],
+                options: InlineAsmOptions::empty(),
+                line_spans: &[],
+                destination: Some(target),
+                unwind,
+            },
+            20,
+        ),
+    ];
+    for (kind, cost) in cases {
+        assert_eq!(kind.cost(), cost, "{kind:?}");
+    }
+
+    // A switch costs one more than its number of targets, counting the otherwise target.
+    let discr = Operand::Copy(Place::from(Local::from_u32(1)));
+    let switch = |n: u32| TerminatorKind::SwitchInt {
+        discr: discr.clone(),
+        targets: SwitchTargets::new(
+            (0..n).map(|i| (i.into(), BasicBlock::from_u32(i))),
+            START_BLOCK,
+        ),
+    };
+    for n in 0..8 {
+        assert_eq!(switch(n).cost(), n as usize + 2, "{n} values");
+    }
+}
+
+#[test]
+fn diverging_terminators() {
+    let place = Place::from(Local::from_u32(1));
+    let target = BasicBlock::from_u32(1);
+    let call = |target| TerminatorKind::Call {
+        func: Operand::Copy(place),
+        args: vec![],
+        destination: place,
+        target,
+        unwind: UnwindAction::Continue,
+        call_source: CallSource::Normal,
+        fn_span: DUMMY_SP,
+    };
+    let inline_asm = |destination| TerminatorKind::InlineAsm {
+        template: &[],
+        operands: vec![],
+        options: InlineAsmOptions::empty(),
+        line_spans: &[],
+        destination,
+        unwind: UnwindAction::Continue,
+    };
+
+    let diverging = [
+        TerminatorKind::Return,
+        TerminatorKind::TailCall { func: Operand::Copy(place), args: vec![], fn_span: DUMMY_SP },
+        TerminatorKind::UnwindResume,
+        TerminatorKind::UnwindTerminate(UnwindTerminateReason::Abi),
+        TerminatorKind::Unreachable,
+        TerminatorKind::CoroutineDrop,
+        call(None),
+        inline_asm(None),
+    ];
+    for kind in diverging {
+        assert!(kind.is_diverging(), "{kind:?}");
+    }
+
+    let returning = [
+        TerminatorKind::Goto { target },
+        TerminatorKind::Drop { place, target, unwind: UnwindAction::Continue, replace: false },
+        TerminatorKind::FalseEdge { real_target: target, imaginary_target: target },
+        TerminatorKind::FalseUnwind { real_target: target, unwind: UnwindAction::Continue },
+        call(Some(target)),
+        inline_asm(Some(target)),
+    ];
+    for kind in returning {
+        assert!(!kind.is_diverging(), "{kind:?}");
+    }
+}
+
+#[test]
+fn assert_kind_operands() {
+    let cases: [(AssertKind<u32>, &[u32]); 8] = [
+        (AssertKind::BoundsCheck { len: 1, index: 2 }, &[1, 2]),
+        (AssertKind::Overflow(BinOp::Add, 1, 2), &[1, 2]),
+        (AssertKind::OverflowNeg(1), &[1]),
+        (AssertKind::DivisionByZero(1), &[1]),
+        (AssertKind::RemainderByZero(1), &[1]),
+        (AssertKind::ResumedAfterReturn(CoroutineKind::Coroutine), &[]),
+        (AssertKind::ResumedAfterPanic(CoroutineKind::Coroutine), &[]),
+        (AssertKind::MisalignedPointerDereference { required: 1, found: 2 }, &[1, 2]),
+    ];
+    for (kind, expected) in cases {
+        let operands: Vec<u32> = kind.operands().into_iter().copied().collect();
+        assert_eq!(operands, expected, "{kind:?}");
+    }
+}
+
+#[test]
+fn overflow_checks() {
+    let coroutine = CoroutineKind::Coroutine;
+    // (kind, is_overflow_check, is_optional_overflow_check)
+    let cases: [(AssertKind<u32>, bool, bool); 10] = [
+        (AssertKind::BoundsCheck { len: 1, index: 2 }, false, false),
+        (AssertKind::Overflow(BinOp::Add, 1, 2), true, true),
+        (AssertKind::Overflow(BinOp::Shl, 1, 2), true, true),
+        // `MIN / -1` is UB whether or not overflow checks are enabled.
+        (AssertKind::Overflow(BinOp::Div, 1, 2), true, false),
+        (AssertKind::OverflowNeg(1), true, true),
+        (AssertKind::DivisionByZero(1), true, false),
+        (AssertKind::RemainderByZero(1), true, false),
+        (AssertKind::ResumedAfterReturn(coroutine), false, false),
+        (AssertKind::ResumedAfterPanic(coroutine), false, false),
+        (AssertKind::MisalignedPointerDereference { required: 1, found: 2 }, false, false),
+    ];
+    for (kind, is_overflow_check, is_optional) in cases {
+        assert_eq!(kind.is_overflow_check(), is_overflow_check, "{kind:?}");
+        assert_eq!(kind.is_optional_overflow_check(), is_optional, "{kind:?}");
+    }
+}
+
+#[test]
+fn call_args_accessors() {
+    let place = Place::from(Local::from_u32(1));
+    let target = BasicBlock::from_u32(1);
+    let mut call = TerminatorKind::Call {
+        func: Operand::Copy(place),
+        args: vec![],
+        destination: place,
+        target: Some(target),
+        unwind: UnwindAction::Continue,
+        call_source: CallSource::Normal,
+        fn_span: DUMMY_SP,
+    };
+
+    let args = call.call_args_mut().unwrap();
+    args.extend((1..=3).map(|i| Operand::Copy(Place::from(Local::from_u32(i)))));
+    args.push(Operand::Move(Place::from(Local::from_u32(4))));
+
+    let args = call.call_args().unwrap();
+    assert_eq!(args.len(), 4);
+    assert!(args[3].is_move());
+    assert_eq!(args[3].place(), Some(Place::from(Local::from_u32(4))));
+
+    // Tail calls are left out, although they have arguments too.
+    let args = vec![Operand::Move(place)];
+    for mut kind in [
+        TerminatorKind::TailCall { func: Operand::Copy(place), args, fn_span: DUMMY_SP },
+        TerminatorKind::Goto { target },
+        TerminatorKind::Return,
+    ] {
+        assert!(kind.call_args().is_none(), "{kind:?}");
+        assert!(kind.call_args_mut().is_none(), "{kind:?}");
+    }
+}
+
+#[test]
+fn prune_switch_targets() {
+    let bb = BasicBlock::from_u32;
+    let targets = SwitchTargets::new([(0, bb(1)), (1, bb(2)), (2, bb(3))].into_iter(), bb(0));
+    let mut kind = TerminatorKind::SwitchInt {
+        discr: Operand::Copy(Place::from(Local::from_u32(1))),
+        targets,
+    };
+
+    kind.prune_switch_targets(|value| value == 1);
+    let (_, targets) = kind.as_switch().unwrap();
+    assert_eq!(targets.all_values(), [1]);
+    assert_eq!(targets.all_targets(), [bb(2), bb(0)]);
+
+    kind.prune_switch_targets(|_| false);
+    assert_eq!(kind.as_goto(), Some(bb(0)));
+
+    // Other terminators are left alone.
+    kind.prune_switch_targets(|_| false);
+    assert_eq!(kind.as_goto(), Some(bb(0)));
+}
+
+#[test]
+fn negate_assert() {
+    let cond = Operand::Copy(Place::from(Local::from_u32(1)));
+    let msg = Box::new(AssertKind::OverflowNeg(cond.clone()));
+    let target = BasicBlock::from_u32(1);
+    let unwind = UnwindAction::Continue;
+    let mut kind = TerminatorKind::Assert { cond, expected: true, msg, target, unwind };
+    let original = kind.clone();
+
+    kind.negate_assert();
+    let TerminatorKind::Assert { expected, .. } = kind else { unreachable!() };
+    assert!(!expected);
+    assert_eq!(kind.successors().collect::<Vec<_>>(), original.successors().collect::<Vec<_>>());
+    kind.negate_assert();
+    assert_eq!(kind, original);
+
+    // Other terminators are left alone.
+    let mut goto = TerminatorKind::Goto { target };
+    goto.negate_assert();
+    assert_eq!(goto, TerminatorKind::Goto { target });
+}
+
+#[test]
+fn inline_asm_referenced_def_ids() {
+    use rustc_hir::def_id::CRATE_DEF_ID;
+    use rustc_target::asm::{InlineAsmRegClass, InlineAsmRegOrRegClass, X86InlineAsmRegClass};
+
+    let reg = InlineAsmRegOrRegClass::RegClass(InlineAsmRegClass::X86(X86InlineAsmRegClass::reg));
+    let def_id = CRATE_DEF_ID.to_def_id();
+    let place = Place::from(Local::from_u32(1));
+    let inline_asm = |operands| TerminatorKind::InlineAsm {
+        template: &[],
+        operands,
+        options: InlineAsmOptions::empty(),
+        line_spans: &[],
+        destination: Some(BasicBlock::from_u32(1)),
+        unwind: UnwindAction::Continue,
+    };
+
+    // Register operands do not reference items.
+    let operands = vec![
+        InlineAsmOperand::SymStatic { def_id },
+        InlineAsmOperand::In { reg, value: Operand::Copy(place) },
+        InlineAsmOperand::Out { reg, late: false, place: Some(place) },
+        InlineAsmOperand::SymStatic { def_id },
+    ];
+    assert_eq!(inline_asm(operands).inline_asm_referenced_def_ids().as_slice(), [def_id, def_id]);
+    assert!(inline_asm(vec![]).inline_asm_referenced_def_ids().is_empty());
+
+    // Only inline assembly is looked at, not the callee of a call.
+    let call = TerminatorKind::Call {
+        func: Operand::Copy(place),
+        args: vec![],
+        destination: place,
+        target: None,
+        unwind: UnwindAction::Continue,
+        call_source: CallSource::Normal,
+        fn_span: DUMMY_SP,
+    };
+    assert!(call.inline_asm_referenced_def_ids().is_empty());
+}
+
+#[test]
+fn inline_asm_options() {
+    for (options, may_unwind, noreturn) in [
+        (InlineAsmOptions::empty(), false, false),
+        (InlineAsmOptions::MAY_UNWIND, true, false),
+        (InlineAsmOptions::NORETURN, false, true),
+        (InlineAsmOptions::MAY_UNWIND | InlineAsmOptions::NORETURN, true, true),
+        (InlineAsmOptions::NOMEM | InlineAsmOptions::NOSTACK, false, false),
+    ] {
+        let kind = TerminatorKind::InlineAsm {
+            template: &[],
+            operands: vec![],
+            options,
+            line_spans: &[],
+            destination: None,
+            unwind: UnwindAction::Unreachable,
+        };
+        assert_eq!(kind.inline_asm_may_unwind(), may_unwind, "{options:?}");
+        assert_eq!(kind.inline_asm_is_noreturn(), noreturn, "{options:?}");
+    }
+
+    // Other terminators have no options, even when they unwind or do not return.
+    let place = Place::from(Local::from_u32(1));
+    let call = TerminatorKind::Call {
+        func: Operand::Copy(place),
+        args: vec![],
+        destination: place,
+        target: None,
+        unwind: UnwindAction::Continue,
+        call_source: CallSource::Normal,
+        fn_span: DUMMY_SP,
+    };
+    for kind in [call, TerminatorKind::UnwindResume, TerminatorKind::Unreachable] {
+        assert!(!kind.inline_asm_may_unwind(), "{kind:?}");
+        assert!(!kind.inline_asm_is_noreturn(), "{kind:?}");
+    }
+}
+
+#[test]
+fn assert_kind_map() {
+    let coroutine = CoroutineKind::Coroutine;
+    let cases: [(AssertKind<u32>, AssertKind<String>); 8] = [
+        (
+            AssertKind::BoundsCheck { len: 1, index: 2 },
+            AssertKind::BoundsCheck { len: "1".into(), index: "2".into() },
+        ),
+        (
+            AssertKind::Overflow(BinOp::Shl, 1, 2),
+            AssertKind::Overflow(BinOp::Shl, "1".into(), "2".into()),
+        ),
+        (AssertKind::OverflowNeg(1), AssertKind::OverflowNeg("1".into())),
+        (AssertKind::DivisionByZero(1), AssertKind::DivisionByZero("1".into())),
+        (AssertKind::RemainderByZero(1), AssertKind::RemainderByZero("1".into())),
+        (AssertKind::ResumedAfterReturn(coroutine), AssertKind::ResumedAfterReturn(coroutine)),
+        (AssertKind::ResumedAfterPanic(coroutine), AssertKind::ResumedAfterPanic(coroutine)),
+        (
+            AssertKind::MisalignedPointerDereference { required: 1, found: 2 },
+            AssertKind::MisalignedPointerDereference { required: "1".into(), found: "2".into() },
+        ),
+    ];
+    for (kind, expected) in cases {
+        let mut seen = vec![];
+        let mapped = kind.clone().map(|op| {
+            seen.push(op);
+            op.to_string()
+        });
+        assert_eq!(mapped, expected, "{kind:?}");
+        assert_eq!(seen, kind.operands().into_iter().copied().collect::<Vec<_>>(), "{kind:?}");
+    }
+}
+
+#[test]
+fn drop_accessors() {
+    let place = Place::from(Local::from_u32(1));
+    let target = BasicBlock::from_u32(1);
+    for replace in [false, true] {
+        let kind = TerminatorKind::Drop { place, target, unwind: UnwindAction::Continue, replace };
+        assert_eq!(kind.drop_place(), Some(&place));
+        assert_eq!(kind.drop_target(), Some(target));
+    }
+
+    // Coroutine drops and unwinding do not drop a place.
+    for kind in [
+        TerminatorKind::CoroutineDrop,
+        TerminatorKind::UnwindResume,
+        TerminatorKind::Goto { target },
+    ] {
+        assert_eq!(kind.drop_place(), None, "{kind:?}");
+        assert_eq!(kind.drop_target(), None, "{kind:?}");
+    }
+}
+
+#[test]
+fn switch_accessors() {
+    let arms =
+        [(0, BasicBlock::from_u32(1)), (3, BasicBlock::from_u32(2)), (7, BasicBlock::from_u32(1))];
+    let otherwise = BasicBlock::from_u32(4);
+    let kind = TerminatorKind::SwitchInt {
+        discr: Operand::Copy(Place::from(Local::from_u32(1))),
+        targets: SwitchTargets::new(arms.into_iter(), otherwise),
+    };
+    assert_eq!(kind.switch_targets_iter().unwrap().collect::<Vec<_>>(), arms);
+    assert_eq!(kind.switch_otherwise(), Some(otherwise));
+
+    // Other terminators with targets are not switches.
+    let target = BasicBlock::from_u32(1);
+    for kind in [
+        TerminatorKind::FalseEdge { real_target: target, imaginary_target: otherwise },
+        TerminatorKind::Goto { target },
+    ] {
+        assert!(kind.switch_targets_iter().is_none(), "{kind:?}");
+        assert_eq!(kind.switch_otherwise(), None, "{kind:?}");
+    }
+}
+
+#[test]
+fn cloned_with_blocks() {
+    let place = Place::from(Local::from_u32(1));
+    let call = TerminatorKind::Call {
+        func: Operand::Copy(place),
+        args: vec![Operand::Move(place)],
+        destination: place,
+        target: Some(BasicBlock::from_u32(1)),
+        unwind: UnwindAction::Cleanup(BasicBlock::from_u32(2)),
+        call_source: CallSource::Normal,
+        fn_span: DUMMY_SP,
+    };
+    let original = call.clone();
+
+    let cloned = call.cloned_with_blocks(|bb| BasicBlock::from_u32(bb.as_u32() + 10));
+    assert_eq!(call, original);
+    assert_eq!(
+        cloned.successors().collect::<Vec<_>>(),
+        [BasicBlock::from_u32(11), BasicBlock::from_u32(12)]
+    );
+    assert_eq!(cloned.call_args(), original.call_args());
+
+    for (kind, _) in matrix() {
+        let cloned = kind.cloned_with_blocks(|bb| bb);
+        assert_eq!(cloned, kind, "`{}`", kind.name());
+    }
+}
+
+#[test]
+fn yield_accessors() {
+    let resume_arg = Place::from(Local::from_u32(2));
+    let resume = BasicBlock::from_u32(1);
+    let drop = BasicBlock::from_u32(2);
+    let kind = |drop| TerminatorKind::Yield {
+        value: Operand::Copy(Place::from(Local::from_u32(1))),
+        resume,
+        resume_arg,
+        drop,
+    };
+    assert_eq!(kind(Some(drop)).yield_resume(), Some((resume, &resume_arg)));
+    assert_eq!(kind(Some(drop)).yield_drop(), Some(drop));
+    assert_eq!(kind(None).yield_drop(), None);
+
+    // Coroutine drops and returns end the coroutine rather than suspending it.
+    for kind in [TerminatorKind::CoroutineDrop, TerminatorKind::Return] {
+        assert_eq!(kind.yield_resume(), None, "{kind:?}");
+        assert_eq!(kind.yield_drop(), None, "{kind:?}");
+    }
+}
@@ -0,0 +1,251 @@
+use super::*;
+
+#[test]
+fn phases_in_order() {
+    let phases: Vec<_> = MirPhase::all().collect();
+    assert_eq!(
+        phases,
+        [
+            MirPhase::Built,
+            MirPhase::Analysis(AnalysisPhase::Initial),
+            MirPhase::Analysis(AnalysisPhase::PostCleanup),
+            MirPhase::Runtime(RuntimePhase::Initial),
+            MirPhase::Runtime(RuntimePhase::PostCleanup),
+            MirPhase::Runtime(RuntimePhase::Optimized),
+        ]
+    );
+    for pair in phases.windows(2) {
+        assert!(pair[0] < pair[1]);
+        assert_eq!(pair[0].phase_index() + 1, pair[1].phase_index());
+        assert_eq!(pair[0].next(), Some(pair[1]));
+    }
+    assert_eq!(MirPhase::Runtime(RuntimePhase::Optimized).next(), None);
+}
+
+#[test]
+fn operators_to_hir() {
+    let binops = [
+        (BinOp::Add, Some(hir::BinOpKind::Add)),
+        (BinOp::AddUnchecked, None),
+        (BinOp::Sub, Some(hir::BinOpKind::Sub)),
+        (BinOp::SubUnchecked, None),
+        (BinOp::Mul, Some(hir::BinOpKind::Mul)),
+        (BinOp::MulUnchecked, None),
+        (BinOp::Div, Some(hir::BinOpKind::Div)),
+        (BinOp::Rem, Some(hir::BinOpKind::Rem)),
+        (BinOp::BitXor, Some(hir::BinOpKind::BitXor)),
+        (BinOp::BitAnd, Some(hir::BinOpKind::BitAnd)),
+        (BinOp::BitOr, Some(hir::BinOpKind::BitOr)),
+        (BinOp::Shl, Some(hir::BinOpKind::Shl)),
+        (BinOp::ShlUnchecked, None),
+        (BinOp::Shr, Some(hir::BinOpKind::Shr)),
+        (BinOp::ShrUnchecked, None),
+        (BinOp::Eq, Some(hir::BinOpKind::Eq)),
+        (BinOp::Lt, Some(hir::BinOpKind::Lt)),
+        (BinOp::Le, Some(hir::BinOpKind::Le)),
+        (BinOp::Ne, Some(hir::BinOpKind::Ne)),
+        (BinOp::Ge, Some(hir::BinOpKind::Ge)),
+        (BinOp::Gt, Some(hir::BinOpKind::Gt)),
+        (BinOp::Offset, None),
+    ];
+    for (op, expected) in binops {
+        assert_eq!(op.to_hir_binop(), expected, "{op:?}");
+    }
+
+    assert_eq!(UnOp::Not.to_hir_unop(), hir::UnOp::Not);
+    assert_eq!(UnOp::Neg.to_hir_unop(), hir::UnOp::Neg);
+}
+
+#[test]
+fn phase_changes() {
+    let phases: Vec<_> = MirPhase::all().collect();
+    for (i, &from) in phases.iter().enumerate() {
+        for (j, &to) in phases.iter().enumerate() {
+            let skips_promotion = from < CONSTS_PROMOTED && to > CONSTS_PROMOTED;
+            assert_eq!(
+                mir_phase_change_is_valid(from, to),
+                i < j && !skips_promotion,
+                "{from:?} -> {to:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn eval_comparisons() {
+    let minus_one = -1i128 as u128;
+    assert_eq!(BinOp::Lt.eval_bool(minus_one, 0, true), Some(true));
+    assert_eq!(BinOp::Lt.eval_bool(minus_one, 0, false), Some(false));
+    assert_eq!(BinOp::Ge.eval_bool(minus_one, 0, false), Some(true));
+
+    // The results of `1 op 2` and of `2 op 2`.
+    for (op, less, equal) in [
+        (BinOp::Eq, false, true),
+        (BinOp::Ne, true, false),
+        (BinOp::Lt, true, false),
+        (BinOp::Le, true, true),
+        (BinOp::Gt, false, false),
+        (BinOp::Ge, false, true),
+    ] {
+        assert_eq!(op.eval_bool(1, 2, false), Some(less), "{op:?}");
+        assert_eq!(op.eval_bool(2, 2, false), Some(equal), "{op:?}");
+    }
+
+    assert_eq!(BinOp::Add.eval_bool(1, 2, false), None);
+    assert_eq!(BinOp::Offset.eval_bool(1, 2, false), None);
+}
+
+#[test]
+fn arith_kinds() {
+    let pairs = [
+        (BinOp::Add, BinOp::AddUnchecked),
+        (BinOp::Sub, BinOp::SubUnchecked),
+        (BinOp::Mul, BinOp::MulUnchecked),
+        (BinOp::Shl, BinOp::ShlUnchecked),
+        (BinOp::Shr, BinOp::ShrUnchecked),
+    ];
+    for (wrapping, unchecked) in pairs {
+        assert_eq!(wrapping.arith_kind(), Some(ArithKind::Wrapping));
+        assert_eq!(unchecked.arith_kind(), Some(ArithKind::Unchecked));
+        for op in [wrapping, unchecked] {
+            assert_eq!(op.with_arith_kind(ArithKind::Wrapping), Some(wrapping), "{op:?}");
+            assert_eq!(op.with_arith_kind(ArithKind::Unchecked), Some(unchecked), "{op:?}");
+            assert_eq!(op.with_arith_kind(ArithKind::Saturating), None, "{op:?}");
+            assert_eq!(op.with_arith_kind(op.arith_kind().unwrap()), Some(op));
+        }
+    }
+
+    for op in [BinOp::Div, BinOp::BitAnd, BinOp::Eq, BinOp::Offset] {
+        assert_eq!(op.arith_kind(), None, "{op:?}");
+        assert_eq!(op.with_arith_kind(ArithKind::Wrapping), None, "{op:?}");
+    }
+}
+
+#[test]
+fn rvalue_place_contexts() {
+    use visit::{RvalueContext, Visitor};
+
+    #[derive(Default)]
+    struct Uses {
+        read: Vec<Local>,
+        address: Vec<Local>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for Uses {
+        fn visit_rvalue_place(&mut self, place: &Place<'tcx>, context: RvalueContext, _: Location) {
+            match context {
+                RvalueContext::Read => self.read.push(place.local),
+                RvalueContext::Address => self.address.push(place.local),
+            }
+        }
+    }
+
+    // References need a region, so `&raw const` stands in for `&` here.
+    let local = |i| Place::from(Local::from_u32(i));
+    let rvalues = [
+        Rvalue::AddressOf(Mutability::Not, local(1)),
+        Rvalue::BinaryOp(BinOp::Add, Box::new((Operand::Copy(local(1)), Operand::Copy(local(2))))),
+        Rvalue::Len(local(3)),
+        Rvalue::Use(Operand::Move(local(4))),
+    ];
+    let mut uses = Uses::default();
+    for rvalue in &rvalues {
+        uses.visit_rvalue_with_context(rvalue, Location::START);
+    }
+    assert_eq!(uses.read, [1, 2, 4].map(Local::from_u32));
+    assert_eq!(uses.address, [1, 3].map(Local::from_u32));
+}
+
+#[test]
+fn inline_asm_operand_places() {
+    use rustc_ast::InlineAsmOptions;
+    use rustc_hir::def_id::CRATE_DEF_ID;
+    use rustc_target::asm::{InlineAsmRegClass, InlineAsmRegOrRegClass, X86InlineAsmRegClass};
+    use visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+
+    #[derive(Default)]
+    struct AsmPlaces {
+        operands: usize,
+        places: Vec<(Local, PlaceContext)>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for AsmPlaces {
+        fn visit_inline_asm_operand(
+            &mut self,
+            operand: &InlineAsmOperand<'tcx>,
+            location: Location,
+        ) {
+            self.operands += 1;
+            self.super_inline_asm_operand(operand, location);
+        }
+
+        fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+            self.places.push((place.local, context));
+        }
+    }
+
+    let reg = InlineAsmRegOrRegClass::RegClass(InlineAsmRegClass::X86(X86InlineAsmRegClass::reg));
+    let local = |i| Place::from(Local::from_u32(i));
+    let operands = vec![
+        InlineAsmOperand::In { reg, value: Operand::Copy(local(1)) },
+        InlineAsmOperand::Out { reg, late: false, place: Some(local(2)) },
+        InlineAsmOperand::Out { reg, late: true, place: None },
+        InlineAsmOperand::InOut {
+            reg,
+            late: false,
+            in_value: Operand::Move(local(3)),
+            out_place: Some(local(4)),
+        },
+        InlineAsmOperand::SymStatic { def_id: CRATE_DEF_ID.to_def_id() },
+    ];
+    let terminator = Terminator {
+        source_info: SourceInfo::outermost(rustc_span::DUMMY_SP),
+        kind: TerminatorKind::InlineAsm {
+            template: &[],
+            operands,
+            options: InlineAsmOptions::empty(),
+            line_spans: &[],
+            destination: Some(BasicBlock::from_u32(1)),
+            unwind: UnwindAction::Unreachable,
+        },
+    };
+
+    let mut visitor = AsmPlaces::default();
+    visitor.visit_terminator(&terminator, Location::START);
+    assert_eq!(visitor.operands, 5);
+    let output = PlaceContext::MutatingUse(MutatingUseContext::AsmOutput);
+    assert_eq!(
+        visitor.places,
+        [
+            (Local::from_u32(1), PlaceContext::NonMutatingUse(NonMutatingUseContext::Copy)),
+            (Local::from_u32(2), output),
+            (Local::from_u32(3), PlaceContext::NonMutatingUse(NonMutatingUseContext::Move)),
+            (Local::from_u32(4), output),
+        ]
+    );
+}
+
+#[test]
+fn phase_names_round_trip() {
+    for phase in MirPhase::all() {
+        assert_eq!(phase.to_string(), phase.name());
+        assert_eq!(phase.to_string().parse(), Ok(phase), "{phase:?}");
+    }
+    assert_eq!("runtime-post-cleanup".parse(), Ok(MirPhase::Runtime(RuntimePhase::PostCleanup)));
+
+    for unknown in ["", "Built", "runtime-initial", "analysis-optimized", "runtime-optimized "] {
+        assert_eq!(unknown.parse::<MirPhase>(), Err(()), "{unknown:?}");
+    }
+}
+
+#[test]
+fn phase_changes_go_through_consts_promoted() {
+    assert!(mir_phase_change_is_valid(MirPhase::Built, CONSTS_PROMOTED));
+    assert!(mir_phase_change_is_valid(CONSTS_PROMOTED, MirPhase::Runtime(RuntimePhase::Optimized)));
+    assert!(!mir_phase_change_is_valid(MirPhase::Built, MirPhase::Runtime(RuntimePhase::Initial)));
+    assert!(!mir_phase_change_is_valid(
+        MirPhase::Built,
+        MirPhase::Analysis(AnalysisPhase::PostCleanup)
+    ));
+    assert!(!mir_phase_change_is_valid(CONSTS_PROMOTED, MirPhase::Built));
+}
@@ -130,6 +130,14 @@ fn visit_assert_message(
                 self.super_assert_message(msg, location);
             }
 
+            fn visit_inline_asm_operand(
+                &mut self,
+                operand: & $($mutability)? InlineAsmOperand<'tcx>,
+                location: Location,
+            ) {
+                self.super_inline_asm_operand(operand, location);
+            }
+
             fn visit_rvalue(
                 &mut self,
                 rvalue: & $($mutability)? Rvalue<'tcx>,
@@ -138,6 +146,26 @@ fn visit_rvalue(
                 self.super_rvalue(rvalue, location);
             }
 
+            /// Visits the places an rvalue operates on, telling apart those whose value is read
+            /// from those of which only the address is used. This is not called by
+            /// `super_rvalue`; analyses that need it should call it from `visit_rvalue`.
+            fn visit_rvalue_with_context(
+                &mut self,
+                rvalue: & $($mutability)? Rvalue<'tcx>,
+                location: Location,
+            ) {
+                self.super_rvalue_with_context(rvalue, location);
+            }
+
+            fn visit_rvalue_place(
+                &mut self,
+                place: & $($mutability)? Place<'tcx>,
+                context: RvalueContext,
+                location: Location,
+            ) {
+                self.super_rvalue_place(place, context, location);
+            }
+
             fn visit_operand(
                 &mut self,
                 operand: & $($mutability)? Operand<'tcx>,
@@ -535,6 +563,17 @@ fn super_terminator(&mut self,
                         );
                     }
 
+                    TerminatorKind::TailCall {
+                        func,
+                        args,
+                        fn_span: _,
+                    } => {
+                        self.visit_operand(func, location);
+                        for arg in args {
+                            self.visit_operand(arg, location);
+                        }
+                    }
+
                     TerminatorKind::Assert {
                         cond,
                         expected: _,
@@ -569,36 +608,42 @@ fn super_terminator(&mut self,
                         unwind: _,
                     } => {
                         for op in operands {
-                            match op {
-                                InlineAsmOperand::In { value, .. } => {
-                                    self.visit_operand(value, location);
-                                }
-                                InlineAsmOperand::Out { place: Some(place), .. } => {
-                                    self.visit_place(
-                                        place,
-                                        PlaceContext::MutatingUse(MutatingUseContext::AsmOutput),
-                                        location,
-                                    );
-                                }
-                                InlineAsmOperand::InOut { in_value, out_place, .. } => {
-                                    self.visit_operand(in_value, location);
-                                    if let Some(out_place) = out_place {
-                                        self.visit_place(
-                                            out_place,
-                                            PlaceContext::MutatingUse(MutatingUseContext::AsmOutput),
-                                            location,
-                                        );
-                                    }
-                                }
-                                InlineAsmOperand::Const { value }
-                                | InlineAsmOperand::SymFn { value } => {
-                                    self.visit_constant(value, location);
-                                }
-                                InlineAsmOperand::Out { place: None, .. }
-                                | InlineAsmOperand::SymStatic { def_id: _ } => {}
-                            }
+                            self.visit_inline_asm_operand(op, location);
+                        }
+                    }
+                }
+            }
+
+            fn super_inline_asm_operand(&mut self,
+                                        operand: & $($mutability)? InlineAsmOperand<'tcx>,
+                                        location: Location) {
+                match operand {
+                    InlineAsmOperand::In { value, .. } => {
+                        self.visit_operand(value, location);
+                    }
+                    InlineAsmOperand::Out { place: Some(place), .. } => {
+                        self.visit_place(
+                            place,
+                            PlaceContext::MutatingUse(MutatingUseContext::AsmOutput),
+                            location,
+                        );
+                    }
+                    InlineAsmOperand::InOut { in_value, out_place, .. } => {
+                        self.visit_operand(in_value, location);
+                        if let Some(out_place) = out_place {
+                            self.visit_place(
+                                out_place,
+                                PlaceContext::MutatingUse(MutatingUseContext::AsmOutput),
+                                location,
+                            );
                         }
                     }
+                    InlineAsmOperand::Const { value }
+                    | InlineAsmOperand::SymFn { value } => {
+                        self.visit_constant(value, location);
+                    }
+                    InlineAsmOperand::Out { place: None, .. }
+                    | InlineAsmOperand::SymStatic { def_id: _ } => {}
                 }
             }
 
@@ -742,6 +787,9 @@ fn super_rvalue(&mut self,
                             ) => {
                                 self.visit_args(coroutine_args, location);
                             }
+                            AggregateKind::RawPtr(ty, _) => {
+                                self.visit_ty($(& $mutability)? *ty, TyContext::Location(location));
+                            }
                         }
 
                         for operand in operands {
@@ -756,6 +804,60 @@ fn super_rvalue(&mut self,
                 }
             }
 
+            fn super_rvalue_with_context(&mut self,
+                                         rvalue: & $($mutability)? Rvalue<'tcx>,
+                                         location: Location) {
+                let visit_operand = |this: &mut Self, operand: & $($mutability)? Operand<'tcx>| {
+                    match operand {
+                        Operand::Copy(place) | Operand::Move(place) => {
+                            this.visit_rvalue_place(place, RvalueContext::Read, location);
+                        }
+                        Operand::Constant(_) => {}
+                    }
+                };
+
+                match rvalue {
+                    Rvalue::Use(operand)
+                    | Rvalue::Repeat(operand, _)
+                    | Rvalue::Cast(_, operand, _)
+                    | Rvalue::UnaryOp(_, operand)
+                    | Rvalue::ShallowInitBox(operand, _) => {
+                        visit_operand(self, operand);
+                    }
+
+                    Rvalue::BinaryOp(_, box (lhs, rhs))
+                    | Rvalue::CheckedBinaryOp(_, box (lhs, rhs)) => {
+                        visit_operand(self, lhs);
+                        visit_operand(self, rhs);
+                    }
+
+                    Rvalue::Aggregate(_, operands) => {
+                        for operand in operands {
+                            visit_operand(self, operand);
+                        }
+                    }
+
+                    Rvalue::CopyForDeref(place) => {
+                        self.visit_rvalue_place(place, RvalueContext::Read, location);
+                    }
+
+                    Rvalue::Ref(_, _, place)
+                    | Rvalue::AddressOf(_, place)
+                    | Rvalue::Len(place)
+                    | Rvalue::Discriminant(place) => {
+                        self.visit_rvalue_place(place, RvalueContext::Address, location);
+                    }
+
+                    Rvalue::ThreadLocalRef(_) | Rvalue::NullaryOp(_, _) => {}
+                }
+            }
+
+            fn super_rvalue_place(&mut self,
+                                  _place: & $($mutability)? Place<'tcx>,
+                                  _context: RvalueContext,
+                                  _location: Location) {
+            }
+
             fn super_operand(&mut self,
                              operand: & $($mutability)? Operand<'tcx>,
                              location: Location) {
@@ -1322,6 +1424,16 @@ pub enum NonUseContext {
     VarDebugInfo,
 }
 
+/// How an rvalue uses a place it operates on, as passed to `visit_rvalue_place`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RvalueContext {
+    /// The value of the place is read, e.g. by `Use`, `BinaryOp` or `Aggregate`.
+    Read,
+    /// Only the address of the place, or something reachable from it without reading its
+    /// value, is used: by `Ref`, `AddressOf`, `Len` and `Discriminant`.
+    Address,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PlaceContext {
     NonMutatingUse(NonMutatingUseContext),
@@ -69,6 +69,9 @@ pub fn parse_terminator(&self, expr_id: ExprId) -> PResult<TerminatorKind<'tcx>>
             @call(mir_call, args) => {
                 self.parse_call(args)
             },
+            @call(mir_tail_call, args) => {
+                self.parse_tail_call(args)
+            },
             ExprKind::Match { scrutinee, arms, .. } => {
                 let discr = self.parse_operand(*scrutinee)?;
                 self.parse_match(arms, expr.span).map(|t| TerminatorKind::SwitchInt { discr, targets: t })
@@ -171,6 +174,19 @@ fn parse_call(&self, args: &[ExprId]) -> PResult<TerminatorKind<'tcx>> {
         )
     }
 
+    fn parse_tail_call(&self, args: &[ExprId]) -> PResult<TerminatorKind<'tcx>> {
+        parse_by_kind!(self, args[0], _, "tail call",
+            ExprKind::Call { fun, args, fn_span, .. } => {
+                let fun = self.parse_operand(*fun)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.parse_operand(*arg))
+                    .collect::<PResult<Vec<_>>>()?;
+                Ok(TerminatorKind::TailCall { func: fun, args, fn_span: *fn_span })
+            },
+        )
+    }
+
     fn parse_rvalue(&self, expr_id: ExprId) -> PResult<Rvalue<'tcx>> {
         parse_by_kind!(self, expr_id, expr, "rvalue",
             @call(mir_discriminant, args) => self.parse_place(args[0]).map(Rvalue::Discriminant),
@@ -190,7 +206,21 @@ fn parse_rvalue(&self, expr_id: ExprId) -> PResult<Rvalue<'tcx>> {
                 let offset = self.parse_operand(args[1])?;
                 Ok(Rvalue::BinaryOp(BinOp::Offset, Box::new((ptr, offset))))
             },
+            @call(mir_raw_ptr, args) => {
+                let ty::RawPtr(ty::TypeAndMut { ty: pointee_ty, mutbl }) = *expr.ty.kind() else {
+                    return Err(self.expr_error(expr_id, "raw pointer"));
+                };
+                let data = self.parse_operand(args[0])?;
+                let meta = self.parse_operand(args[1])?;
+                Ok(Rvalue::Aggregate(
+                    Box::new(AggregateKind::RawPtr(pointee_ty, mutbl)),
+                    [data, meta].into_iter().collect(),
+                ))
+            },
             @call(mir_len, args) => Ok(Rvalue::Len(self.parse_place(args[0])?)),
+            @call(mir_ub_checks, _args) => {
+                Ok(Rvalue::NullaryOp(NullOp::UbChecks, self.tcx.types.bool))
+            },
             @call(mir_copy_for_deref, args) => Ok(Rvalue::CopyForDeref(self.parse_place(args[0])?)),
             ExprKind::Borrow { borrow_kind, arg } => Ok(
                 Rvalue::Ref(self.tcx.lifetimes.re_erased, *borrow_kind, self.parse_place(*arg)?)
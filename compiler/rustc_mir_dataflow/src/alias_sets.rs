@@ -0,0 +1,60 @@
+//! A conservative partition of the places of a body into sets that may alias each other.
+//!
+//! A place that involves no `Deref` and whose base local is never borrowed can only be reached by
+//! naming that local, so it may only alias other places based on the same local. Every other place
+//! may be reached through a pointer, and all of those are put in a single set.
+
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::*;
+
+use crate::impls::borrowed_locals;
+
+#[cfg(test)]
+mod tests;
+
+/// The alias set of a place. Two places may only alias if they are in the same set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AliasSet {
+    /// A place based on this local without any `Deref`, where the local is never borrowed.
+    Local(Local),
+    /// A place that may be reached through a pointer: it either involves a `Deref`, or its base
+    /// local is borrowed at some point.
+    Indirect,
+}
+
+/// The alias sets of a body, as computed by [`compute_alias_sets`].
+#[derive(Clone, Debug)]
+pub struct AliasSets {
+    borrowed: BitSet<Local>,
+}
+
+/// Computes the alias sets of the places of `body`.
+pub fn compute_alias_sets(body: &Body<'_>) -> AliasSets {
+    AliasSets::new(borrowed_locals(body))
+}
+
+impl AliasSets {
+    /// Creates the alias sets for a body in which exactly the locals in `borrowed` are borrowed.
+    pub fn new(borrowed: BitSet<Local>) -> AliasSets {
+        AliasSets { borrowed }
+    }
+
+    /// The locals that are borrowed at some point in the body.
+    pub fn borrowed_locals(&self) -> &BitSet<Local> {
+        &self.borrowed
+    }
+
+    /// Returns the alias set `place` belongs to.
+    pub fn alias_set(&self, place: PlaceRef<'_>) -> AliasSet {
+        if place.is_indirect() || self.borrowed.contains(place.local) {
+            AliasSet::Indirect
+        } else {
+            AliasSet::Local(place.local)
+        }
+    }
+
+    /// Returns `false` if writing to one of the places can never affect the other.
+    pub fn may_alias(&self, a: PlaceRef<'_>, b: PlaceRef<'_>) -> bool {
+        self.alias_set(a) == self.alias_set(b)
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+fn local(index: u32) -> Local {
+    Local::from_u32(index)
+}
+
+fn alias_sets(borrowed: &[u32]) -> AliasSets {
+    let mut set = BitSet::new_empty(8);
+    for &index in borrowed {
+        set.insert(local(index));
+    }
+    AliasSets::new(set)
+}
+
+#[test]
+fn distinct_locals() {
+    let sets = alias_sets(&[]);
+    let index = [PlaceElem::Index(local(3))];
+    let a = PlaceRef { local: local(1), projection: &[] };
+    let b = PlaceRef { local: local(2), projection: &[] };
+    let a_index = PlaceRef { local: local(1), projection: &index };
+
+    assert_eq!(sets.alias_set(a), AliasSet::Local(local(1)));
+    assert_eq!(sets.alias_set(a_index), AliasSet::Local(local(1)));
+    assert_eq!(sets.alias_set(b), AliasSet::Local(local(2)));
+    assert!(!sets.may_alias(a, b));
+    assert!(!sets.may_alias(a_index, b));
+    assert!(sets.may_alias(a, a_index));
+}
+
+#[test]
+fn derefs_are_grouped() {
+    let sets = alias_sets(&[]);
+    let deref = [PlaceElem::Deref];
+    let a = PlaceRef { local: local(1), projection: &deref };
+    let b = PlaceRef { local: local(2), projection: &deref };
+    let c = PlaceRef { local: local(3), projection: &[] };
+
+    assert_eq!(sets.alias_set(a), AliasSet::Indirect);
+    assert_eq!(sets.alias_set(b), AliasSet::Indirect);
+    assert!(sets.may_alias(a, b));
+    assert!(!sets.may_alias(a, c));
+}
+
+#[test]
+fn borrowed_locals_are_grouped_with_derefs() {
+    let sets = alias_sets(&[2]);
+    let deref = [PlaceElem::Deref];
+    let a = PlaceRef { local: local(1), projection: &deref };
+    let b = PlaceRef { local: local(2), projection: &[] };
+    let c = PlaceRef { local: local(3), projection: &[] };
+
+    assert_eq!(sets.alias_set(b), AliasSet::Indirect);
+    assert!(sets.may_alias(a, b));
+    assert!(!sets.may_alias(b, c));
+}
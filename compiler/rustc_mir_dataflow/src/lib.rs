@@ -24,10 +24,12 @@
 pub use self::framework::{
     fmt, lattice, visit_results, Analysis, AnalysisDomain, Direction, GenKill, GenKillAnalysis,
     JoinSemiLattice, MaybeReachable, Results, ResultsCursor, ResultsVisitable, ResultsVisitor,
+    SwitchIntEdgeEffects,
 };
-use self::framework::{Backward, CloneAnalysis, ResultsClonedCursor, SwitchIntEdgeEffects};
+use self::framework::{Backward, CloneAnalysis, ResultsClonedCursor};
 use self::move_paths::MoveData;
 
+pub mod alias_sets;
 pub mod debuginfo;
 pub mod drop_flag_effects;
 pub mod elaborate_drops;
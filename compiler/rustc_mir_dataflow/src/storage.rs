@@ -1,5 +1,10 @@
-use rustc_index::bit_set::BitSet;
-use rustc_middle::mir::{self, Local};
+use rustc_index::bit_set::{BitMatrix, BitSet};
+use rustc_middle::mir::{self, traversal, Local, Location};
+use rustc_middle::ty::TyCtxt;
+use std::borrow::Cow;
+
+use crate::impls::MaybeStorageLive;
+use crate::Analysis;
 
 /// The set of locals in a MIR body that do not have `StorageLive`/`StorageDead` annotations.
 ///
@@ -18,3 +23,36 @@ pub fn always_storage_live_locals(body: &mir::Body<'_>) -> BitSet<Local> {
 
     always_live_locals
 }
+
+/// Computes which pairs of locals may have storage at the same time according to
+/// `MaybeStorageLive`, i.e. their `StorageLive` and `StorageDead` statements. Locals that do not
+/// conflict can share their storage.
+///
+/// The relation is symmetric, and every local that has storage somewhere conflicts with itself.
+/// Locals without storage annotations conflict with every local. Unreachable code is ignored.
+pub fn storage_conflicts<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+) -> BitMatrix<Local, Local> {
+    let always_live_locals = always_storage_live_locals(body);
+    let mut conflicts = BitMatrix::from_row_n(&always_live_locals, body.local_decls.len());
+
+    let mut maybe_storage_live = MaybeStorageLive::new(Cow::Borrowed(&always_live_locals))
+        .into_engine(tcx, body)
+        .iterate_to_fixpoint()
+        .into_results_cursor(body);
+    for (block, data) in traversal::reachable(body) {
+        for statement_index in 0..=data.statements.len() {
+            maybe_storage_live.seek_before_primary_effect(Location { block, statement_index });
+            let live = maybe_storage_live.get();
+            for local in live.iter() {
+                conflicts.union_row_with(live, local);
+            }
+        }
+    }
+
+    for local in always_live_locals.iter() {
+        conflicts.insert_all_into_row(local);
+    }
+    conflicts
+}
@@ -272,6 +272,7 @@ fn super_terminator<'mir>(
             | TerminatorKind::UnwindResume
             | TerminatorKind::UnwindTerminate(_)
             | TerminatorKind::Return
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Unreachable
             | TerminatorKind::Assert { .. }
             | TerminatorKind::CoroutineDrop
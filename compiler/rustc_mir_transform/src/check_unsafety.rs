@@ -66,7 +66,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 // safe (at least as emitted during MIR construction)
             }
 
-            TerminatorKind::Call { ref func, .. } => {
+            TerminatorKind::Call { ref func, .. } | TerminatorKind::TailCall { ref func, .. } => {
                 let func_ty = func.ty(self.body, self.tcx);
                 let func_id =
                     if let ty::FnDef(func_id, _) = func_ty.kind() { Some(func_id) } else { None };
@@ -118,7 +118,7 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
     fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
         match rvalue {
             Rvalue::Aggregate(box ref aggregate, _) => match aggregate {
-                &AggregateKind::Array(..) | &AggregateKind::Tuple => {}
+                &AggregateKind::Array(..) | &AggregateKind::Tuple | &AggregateKind::RawPtr(..) => {}
                 &AggregateKind::Adt(adt_did, ..) => {
                     match self.tcx.layout_scalar_valid_range(adt_did) {
                         (Bound::Unbounded, Bound::Unbounded) => {}
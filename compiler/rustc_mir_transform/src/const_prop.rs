@@ -423,6 +423,12 @@ fn check_rvalue(&mut self, rvalue: &Rvalue<'tcx>) -> Option<()> {
 
                 return None;
             }
+            // Only known once we know which crate this body gets codegened in.
+            Rvalue::NullaryOp(NullOp::UbChecks, _) => {
+                trace!("skipping UbChecks");
+
+                return None;
+            }
             // There's no other checking to do at this time.
             Rvalue::Aggregate(..)
             | Rvalue::Use(..)
@@ -420,6 +420,12 @@ fn check_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) -> Option<
 
                 return None;
             }
+            // Nothing to lint about, and the value is not known before codegen.
+            Rvalue::NullaryOp(NullOp::UbChecks, _) => {
+                trace!("skipping UbChecks");
+
+                return None;
+            }
 
             // There's no other checking to do at this time.
             Rvalue::Aggregate(..)
@@ -652,6 +658,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
             | TerminatorKind::FalseEdge { .. }
             | TerminatorKind::FalseUnwind { .. }
             | TerminatorKind::Call { .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::InlineAsm { .. } => {}
         }
 
@@ -16,23 +16,22 @@
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
 use rustc_middle::ty::TyCtxt;
+use rustc_mir_dataflow::alias_sets::{compute_alias_sets, AliasSet};
 use rustc_mir_dataflow::debuginfo::debuginfo_locals;
-use rustc_mir_dataflow::impls::{
-    borrowed_locals, LivenessTransferFunction, MaybeTransitiveLiveLocals,
-};
+use rustc_mir_dataflow::impls::{LivenessTransferFunction, MaybeTransitiveLiveLocals};
 use rustc_mir_dataflow::Analysis;
 
 /// Performs the optimization on the body
 ///
-/// The `borrowed` set must be a `BitSet` of all the locals that are ever borrowed in this body. It
-/// can be generated via the [`borrowed_locals`] function.
+/// Only stores to places that are in the alias set of their own local are considered, as any
+/// other place may be read through a pointer.
 pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-    let borrowed_locals = borrowed_locals(body);
+    let alias_sets = compute_alias_sets(body);
 
     // If the user requests complete debuginfo, mark the locals that appear in it as live, so
     // we don't remove assignements to them.
     let mut always_live = debuginfo_locals(body);
-    always_live.union(&borrowed_locals);
+    always_live.union(alias_sets.borrowed_locals());
 
     let mut live = MaybeTransitiveLiveLocals::new(&always_live)
         .into_engine(tcx, body)
@@ -54,10 +53,9 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
 
             for (index, arg) in args.iter().enumerate().rev() {
                 if let Operand::Copy(place) = *arg
-                    && !place.is_indirect()
                     // Do not skip the transformation if the local is in debuginfo, as we do
                     // not really lose any information for this purpose.
-                    && !borrowed_locals.contains(place.local)
+                    && let AliasSet::Local(_) = alias_sets.alias_set(place.as_ref())
                     && !state.contains(place.local)
                     // If `place` is a projection of a disaligned field in a packed ADT,
                     // the move may be codegened as a pointer to that field.
@@ -84,9 +82,11 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                 StatementKind::Assign(box (place, _))
                 | StatementKind::SetDiscriminant { place: box place, .. }
                 | StatementKind::Deinit(box place) => {
-                    if !place.is_indirect() && !always_live.contains(place.local) {
+                    if let AliasSet::Local(local) = alias_sets.alias_set(place.as_ref())
+                        && !always_live.contains(local)
+                    {
                         live.seek_before_primary_effect(loc);
-                        if !live.get().contains(place.local) {
+                        if !live.get().contains(local) {
                             patch.push(loc);
                         }
                     }
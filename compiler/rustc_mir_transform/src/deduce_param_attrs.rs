@@ -109,7 +109,9 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
         // Note that, because the problematic MIR is never actually generated, we can't add a test
         // case for this.
 
-        if let TerminatorKind::Call { ref args, .. } = terminator.kind {
+        if let TerminatorKind::Call { ref args, .. } | TerminatorKind::TailCall { ref args, .. } =
+            terminator.kind
+        {
             for arg in args {
                 if let Operand::Move(place) = *arg {
                     let local = place.local;
@@ -621,6 +621,12 @@ fn for_terminator<'tcx>(&mut self, terminator: &TerminatorKind<'tcx>) {
                     self.add_operand(arg);
                 }
             }
+            TerminatorKind::TailCall { func, args, .. } => {
+                self.add_operand(func);
+                for arg in args {
+                    self.add_operand(arg);
+                }
+            }
             TerminatorKind::InlineAsm { operands, .. } => {
                 for asm_operand in operands {
                     match asm_operand {
@@ -3,16 +3,19 @@
 use rustc_index::bit_set::BitSet;
 use rustc_index::IndexVec;
 use rustc_middle::mir::patch::MirPatch;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_mir_dataflow::elaborate_drops::{elaborate_drop, DropFlagState, Unwind};
 use rustc_mir_dataflow::elaborate_drops::{DropElaborator, DropFlagMode, DropStyle};
+use rustc_mir_dataflow::fmt::DebugWithContext;
 use rustc_mir_dataflow::impls::{MaybeInitializedPlaces, MaybeUninitializedPlaces};
+use rustc_mir_dataflow::lattice::FlatSet;
 use rustc_mir_dataflow::move_paths::{LookupResult, MoveData, MovePathIndex};
 use rustc_mir_dataflow::on_all_children_bits;
 use rustc_mir_dataflow::on_lookup_result_bits;
 use rustc_mir_dataflow::MoveDataParamEnv;
-use rustc_mir_dataflow::{Analysis, ResultsCursor};
+use rustc_mir_dataflow::{Analysis, AnalysisDomain, JoinSemiLattice, ResultsCursor};
 use rustc_span::Span;
 use rustc_target::abi::{FieldIdx, VariantIdx};
 use std::fmt;
@@ -58,7 +61,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         // init/uninit for types that do need dropping.
         let move_data =
             MoveData::gather_moves(body, tcx, param_env, |ty| ty.needs_drop(tcx, param_env));
-        let elaborate_patch = {
+        let (elaborate_patch, drop_flags) = {
             let env = MoveDataParamEnv { move_data, param_env };
 
             let mut inits = MaybeInitializedPlaces::new(tcx, body, &env)
@@ -78,17 +81,25 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                 .into_results_cursor(body);
 
             let drop_flags = IndexVec::from_elem(None, &env.move_data.move_paths);
+            let read_drop_flags = BitSet::new_empty(env.move_data.move_paths.len());
             ElaborateDropsCtxt {
                 tcx,
                 body,
                 env: &env,
                 init_data: InitializationData { inits, uninits },
                 drop_flags,
+                read_drop_flags,
                 patch: MirPatch::new(body),
             }
             .elaborate()
         };
         elaborate_patch.apply(body);
+        let mut drop_flag_set = BitSet::new_empty(body.local_decls.len());
+        for flag in drop_flags {
+            drop_flag_set.insert(flag);
+        }
+        fold_constant_drop_flags(tcx, body, &drop_flag_set);
+        remove_unread_drop_flags(body, &drop_flag_set);
         deref_finder(tcx, body);
     }
 }
@@ -119,6 +130,152 @@ fn compute_dead_unwinds<'mir, 'tcx>(
     dead_unwinds
 }
 
+/// Replaces the tests of drop flags that hold the same value on every path reaching them.
+///
+/// Drop flags follow the init dataflow, except on the edges of a `switchInt` on the discriminant
+/// of an enum: there, the fields of the inactive variants become maybe-uninitialized while their
+/// flags are left untouched. A field that is initialized whenever its variant is active thus gets
+/// a flag which is always set where it is tested, and its test can be made unconditional.
+fn fold_constant_drop_flags<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    drop_flags: &BitSet<Local>,
+) {
+    let mut flag_values = DropFlagValues { drop_flags }
+        .into_engine(tcx, body)
+        .pass_name("elaborate_drops")
+        .iterate_to_fixpoint()
+        .into_results_cursor(body);
+    let mut constant_tests = Vec::new();
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        let TerminatorKind::SwitchInt { discr: Operand::Copy(place), ref targets } =
+            data.terminator().kind
+        else {
+            continue;
+        };
+        let Some(flag) = place.as_local() else { continue };
+        if !drop_flags.contains(flag) {
+            continue;
+        }
+
+        flag_values.seek_before_primary_effect(body.terminator_loc(bb));
+        if let FlatSet::Elem(value) = flag_values.get().0[flag] {
+            constant_tests.push((bb, targets.target_for_value(u128::from(value))));
+        }
+    }
+
+    for (bb, target) in constant_tests {
+        body.basic_blocks_mut()[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+    }
+}
+
+/// Removes the assignments to drop flags that are never read.
+///
+/// A flag is created for every move path that is maybe-initialized and maybe-uninitialized at a
+/// drop, but an open drop only tests the flags of the fields it drops. When every field of a
+/// partially-moved value has its own move path, the flag of the value itself is only ever written.
+/// Flags whose tests were folded by `fold_constant_drop_flags` are not read anymore either.
+///
+/// The locals themselves are left for `SimplifyLocals` to clean up: removing them here would
+/// renumber every local declared after them.
+fn remove_unread_drop_flags(body: &mut Body<'_>, drop_flags: &BitSet<Local>) {
+    let mut unread = drop_flags.clone();
+    DropFlagReads { unread: &mut unread }.visit_body(body);
+    if unread.is_empty() {
+        return;
+    }
+    for data in body.basic_blocks.as_mut_preserves_cfg() {
+        data.statements.retain(|statement| match statement.kind {
+            StatementKind::Assign(box (place, _)) => {
+                !place.as_local().is_some_and(|local| unread.contains(local))
+            }
+            _ => true,
+        });
+    }
+}
+
+/// Removes the drop flags that are read from `unread`.
+struct DropFlagReads<'a> {
+    unread: &'a mut BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for DropFlagReads<'_> {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_use() && !context.is_place_assignment() {
+            self.unread.remove(local);
+        }
+    }
+}
+
+/// Computes the value each drop flag holds, when it is the same on every path.
+struct DropFlagValues<'a> {
+    drop_flags: &'a BitSet<Local>,
+}
+
+/// The value of every drop flag, indexed by its local.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FlagValues(IndexVec<Local, FlatSet<bool>>);
+
+impl JoinSemiLattice for FlagValues {
+    fn join(&mut self, other: &Self) -> bool {
+        self.0.join(&other.0)
+    }
+}
+
+impl<C> DebugWithContext<C> for FlagValues {}
+
+impl<'tcx> AnalysisDomain<'tcx> for DropFlagValues<'_> {
+    type Domain = FlagValues;
+
+    const NAME: &'static str = "drop_flag_values";
+
+    fn bottom_value(&self, body: &Body<'tcx>) -> Self::Domain {
+        FlagValues(IndexVec::from_elem(FlatSet::Bottom, &body.local_decls))
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, _: &mut Self::Domain) {
+        // Every drop flag is assigned before it is tested.
+    }
+}
+
+impl<'tcx> Analysis<'tcx> for DropFlagValues<'_> {
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement<'tcx>,
+        _: Location,
+    ) {
+        if let StatementKind::Assign(box (place, ref rvalue)) = statement.kind
+            && let Some(flag) = place.as_local()
+            && self.drop_flags.contains(flag)
+        {
+            state.0[flag] = match rvalue {
+                Rvalue::Use(Operand::Constant(constant)) => {
+                    constant.const_.try_to_bool().map_or(FlatSet::Top, FlatSet::Elem)
+                }
+                _ => FlatSet::Top,
+            };
+        }
+    }
+
+    fn apply_terminator_effect<'mir>(
+        &mut self,
+        _: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        _: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        terminator.edges()
+    }
+
+    fn apply_call_return_effect(
+        &mut self,
+        _: &mut Self::Domain,
+        _: BasicBlock,
+        _: CallReturnPlaces<'_, 'tcx>,
+    ) {
+    }
+}
+
 struct InitializationData<'mir, 'tcx> {
     inits: ResultsCursor<'mir, 'tcx, MaybeInitializedPlaces<'mir, 'tcx>>,
     uninits: ResultsCursor<'mir, 'tcx, MaybeUninitializedPlaces<'mir, 'tcx>>,
@@ -235,7 +392,7 @@ fn downcast_subpath(&self, path: Self::Path, variant: VariantIdx) -> Option<Self
     }
 
     fn get_drop_flag(&mut self, path: Self::Path) -> Option<Operand<'tcx>> {
-        self.ctxt.drop_flag(path).map(Operand::Copy)
+        self.ctxt.read_drop_flag(path).map(Operand::Copy)
     }
 }
 
@@ -245,6 +402,8 @@ struct ElaborateDropsCtxt<'a, 'tcx> {
     env: &'a MoveDataParamEnv<'tcx>,
     init_data: InitializationData<'a, 'tcx>,
     drop_flags: IndexVec<MovePathIndex, Option<Local>>,
+    /// The move paths whose drop flag is tested by some elaborated drop.
+    read_drop_flags: BitSet<MovePathIndex>,
     patch: MirPatch<'tcx>,
 }
 
@@ -263,23 +422,37 @@ fn create_drop_flag(&mut self, index: MovePathIndex, span: Span) {
         self.drop_flags[index].get_or_insert_with(|| patch.new_temp(self.tcx.types.bool, span));
     }
 
-    fn drop_flag(&mut self, index: MovePathIndex) -> Option<Place<'tcx>> {
-        self.drop_flags[index].map(Place::from)
+    fn read_drop_flag(&mut self, index: MovePathIndex) -> Option<Place<'tcx>> {
+        let flag = self.drop_flags[index]?;
+        self.read_drop_flags.insert(index);
+        Some(Place::from(flag))
     }
 
     /// create a patch that elaborates all drops in the input
-    /// MIR.
-    fn elaborate(mut self) -> MirPatch<'tcx> {
+    /// MIR, along with the drop flags it uses.
+    fn elaborate(mut self) -> (MirPatch<'tcx>, Vec<Local>) {
         self.collect_drop_flags();
 
         self.elaborate_drops();
 
+        // All the reads of drop flags happen in `elaborate_drops`, so the flags that have not
+        // been read by now are dead. Forget them so that no more assignments are added for them.
+        let mut drop_flags = Vec::new();
+        for (index, flag) in self.drop_flags.iter_enumerated_mut() {
+            if let Some(local) = *flag {
+                drop_flags.push(local);
+                if !self.read_drop_flags.contains(index) {
+                    *flag = None;
+                }
+            }
+        }
+
         self.drop_flags_on_init();
         self.drop_flags_for_fn_rets();
         self.drop_flags_for_args();
         self.drop_flags_for_locs();
 
-        self.patch
+        (self.patch, drop_flags)
     }
 
     fn collect_drop_flags(&mut self) {
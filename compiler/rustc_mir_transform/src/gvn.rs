@@ -472,6 +472,8 @@ fn eval_to_const(&mut self, value: VnIndex) -> Option<OpTy<'tcx>> {
                     NullOp::OffsetOf(fields) => {
                         layout.offset_of_subfield(&self.ecx, fields.iter()).bytes()
                     }
+                    // Depends on the crate being codegened, so do not fold it.
+                    NullOp::UbChecks => return None,
                 };
                 let usize_layout = self.ecx.layout_of(self.tcx.types.usize).unwrap();
                 let imm = ImmTy::try_from_uint(val, usize_layout)?;
@@ -776,9 +778,14 @@ fn simplify_rvalue(
                 Value::Cast { kind, value, from, to }
             }
             Rvalue::BinaryOp(op, box (ref mut lhs, ref mut rhs)) => {
+                let ty = lhs.ty(self.local_decls, self.tcx);
                 let lhs = self.simplify_operand(lhs, location);
                 let rhs = self.simplify_operand(rhs, location);
-                Value::BinaryOp(op, lhs?, rhs?)
+                let (lhs, rhs) = (lhs?, rhs?);
+                if let Some(value) = self.simplify_fn_ptr_comparison(op, ty, lhs, rhs) {
+                    return Some(value);
+                }
+                Value::BinaryOp(op, lhs, rhs)
             }
             Rvalue::CheckedBinaryOp(op, box (ref mut lhs, ref mut rhs)) => {
                 let lhs = self.simplify_operand(lhs, location);
@@ -804,6 +811,39 @@ fn simplify_rvalue(
         Some(self.insert(value))
     }
 
+    /// Folds the comparison of a function pointer with itself, or with the same pointer cast to a
+    /// raw pointer or an address.
+    ///
+    /// Reifying the same function twice may or may not give the same address, and so may reifying
+    /// two different functions. So this never concludes anything about two distinct values, even
+    /// if they are constants that point to the same function.
+    fn simplify_fn_ptr_comparison(
+        &mut self,
+        op: BinOp,
+        ty: Ty<'tcx>,
+        lhs: VnIndex,
+        rhs: VnIndex,
+    ) -> Option<VnIndex> {
+        if lhs != rhs {
+            return None;
+        }
+        let is_fn_ptr = ty.is_fn_ptr()
+            || match *self.get(lhs) {
+                Value::Cast { kind: CastKind::FnPtrToPtr, .. } => true,
+                Value::Cast { kind: CastKind::PointerExposeAddress, from, .. } => from.is_fn_ptr(),
+                _ => false,
+            };
+        if !is_fn_ptr {
+            return None;
+        }
+        let equal = match op {
+            BinOp::Eq | BinOp::Le | BinOp::Ge => true,
+            BinOp::Ne | BinOp::Lt | BinOp::Gt => false,
+            _ => return None,
+        };
+        Some(self.insert_scalar(Scalar::from_bool(equal), self.tcx.types.bool))
+    }
+
     fn simplify_discriminant(&mut self, place: VnIndex) -> Option<VnIndex> {
         if let Value::Aggregate(enum_ty, variant, _) = *self.get(place)
             && let AggregateTy::Def(enum_did, enum_substs) = enum_ty
@@ -834,6 +874,8 @@ fn simplify_aggregate(
                 AggregateKind::Adt(did, ..) => tcx.def_kind(did) != DefKind::Enum,
                 // Coroutines are never ZST, as they at least contain the implicit states.
                 AggregateKind::Coroutine(..) => false,
+                // Raw pointers always have a data pointer.
+                AggregateKind::RawPtr(..) => false,
             };
 
             if is_zst {
@@ -859,6 +901,8 @@ fn simplify_aggregate(
             }
             // Do not track unions.
             AggregateKind::Adt(_, _, _, _, Some(_)) => return None,
+            // The parts of a raw pointer are not fields that can be projected out of it.
+            AggregateKind::RawPtr(..) => return None,
         };
 
         let fields: Option<Vec<_>> = fields
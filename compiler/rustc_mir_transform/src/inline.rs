@@ -514,6 +514,9 @@ fn check_mir_body(
                 // inline-asm is detected. LLVM will still possibly do an inline later on
                 // if the no-attribute function ends up with the same instruction set anyway.
                 return Err("Cannot move inline-asm across instruction sets");
+            } else if let TerminatorKind::TailCall { .. } = term.kind {
+                // A tail call would replace the caller's frame, not the callee's.
+                return Err("callee contains a tail call");
             } else {
                 work_list.extend(term.successors())
             }
@@ -963,6 +966,8 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, loc: Location)
 
         match terminator.kind {
             TerminatorKind::CoroutineDrop | TerminatorKind::Yield { .. } => bug!(),
+            // `check_mir_body` refuses to inline bodies containing tail calls.
+            TerminatorKind::TailCall { .. } => bug!(),
             TerminatorKind::Goto { ref mut target } => {
                 *target = self.map_block(*target);
             }
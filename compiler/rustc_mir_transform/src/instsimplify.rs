@@ -3,9 +3,11 @@
 use crate::simplify::simplify_duplicate_switch_targets;
 use crate::MirPass;
 use rustc_hir::Mutability;
+use rustc_index::IndexVec;
 use rustc_middle::mir::*;
 use rustc_middle::ty::layout::ValidityRequirement;
 use rustc_middle::ty::{self, GenericArgsRef, ParamEnv, Ty, TyCtxt};
+use rustc_mir_dataflow::alias_sets::{compute_alias_sets, AliasSet};
 use rustc_span::symbol::Symbol;
 use rustc_target::abi::FieldIdx;
 
@@ -22,17 +24,29 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             local_decls: &body.local_decls,
             param_env: tcx.param_env_reveal_all_normalized(body.source.def_id()),
         };
-        for block in body.basic_blocks.as_mut() {
+        let nonzero_on_entry = ctx.nonzero_on_entry(body);
+        for (bb, block) in body.basic_blocks.as_mut().iter_enumerated_mut() {
+            // A local that is known to be nonzero at the current statement.
+            let mut nonzero = nonzero_on_entry[bb];
             for statement in block.statements.iter_mut() {
                 match statement.kind {
                     StatementKind::Assign(box (_place, ref mut rvalue)) => {
                         ctx.simplify_bool_cmp(&statement.source_info, rvalue);
                         ctx.simplify_ref_deref(&statement.source_info, rvalue);
+                        ctx.simplify_addr_of_deref(&statement.source_info, rvalue);
                         ctx.simplify_len(&statement.source_info, rvalue);
                         ctx.simplify_cast(&statement.source_info, rvalue);
+                        ctx.simplify_sub_self(&statement.source_info, rvalue);
+                        ctx.simplify_div_self(&statement.source_info, rvalue, nonzero);
+                        ctx.canonicalize_binop_operands(&statement.source_info, rvalue);
                     }
                     _ => {}
                 }
+                if let Some(local) = nonzero
+                    && writes_local(&statement.kind, local)
+                {
+                    nonzero = None;
+                }
             }
 
             ctx.simplify_primitive_clone(block.terminator.as_mut().unwrap(), &mut block.statements);
@@ -40,6 +54,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                 block.terminator.as_mut().unwrap(),
                 &mut block.statements,
             );
+            ctx.simplify_uninhabited_call(block.terminator.as_mut().unwrap());
             simplify_duplicate_switch_targets(block.terminator.as_mut().unwrap());
         }
     }
@@ -126,6 +141,34 @@ fn simplify_ref_deref(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>
         }
     }
 
+    /// Transform "&raw const (*p)" ==> "p" for a thin `*const T` pointer `p`, and likewise for
+    /// `*mut T`.
+    fn simplify_addr_of_deref(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+        let Rvalue::AddressOf(mutbl, place) = *rvalue else { return };
+        let Some((base, ProjectionElem::Deref)) = place.as_ref().last_projection() else {
+            return;
+        };
+        let base_ty = base.ty(self.local_decls, self.tcx).ty;
+        // Only reuse a pointer of the same mutability, and only for thin pointers so that we do
+        // not have to reason about the metadata.
+        let ty::RawPtr(ty::TypeAndMut { ty: pointee_ty, mutbl: base_mutbl }) = *base_ty.kind()
+        else {
+            return;
+        };
+        if base_mutbl != mutbl || !pointee_ty.is_sized(self.tcx, self.param_env) {
+            return;
+        }
+
+        if !self.should_simplify(source_info, rvalue) {
+            return;
+        }
+
+        *rvalue = Rvalue::Use(Operand::Copy(Place {
+            local: base.local,
+            projection: self.tcx.mk_place_elems(base.projection),
+        }));
+    }
+
     /// Transform "Len([_; N])" ==> "N".
     fn simplify_len(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
         if let Rvalue::Len(ref place) = *rvalue {
@@ -142,6 +185,133 @@ fn simplify_len(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
         }
     }
 
+    /// Transform "Sub(x, x)" ==> "0" for integers.
+    ///
+    /// This is not valid for floats, where `NaN - NaN` and `inf - inf` are `NaN`.
+    fn simplify_sub_self(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+        if let Rvalue::BinaryOp(BinOp::Sub | BinOp::SubUnchecked, box (a, b)) = rvalue
+            && let Some(a_place) = a.place()
+            && b.place() == Some(a_place)
+        {
+            let ty = a.ty(self.local_decls, self.tcx);
+            if !ty.is_integral() || !self.should_simplify(source_info, rvalue) {
+                return;
+            }
+
+            let const_ = Const::from_bits(self.tcx, 0, self.param_env.and(ty));
+            let constant = ConstOperand { span: source_info.span, const_, user_ty: None };
+            *rvalue = Rvalue::Use(Operand::Constant(Box::new(constant)));
+        }
+    }
+
+    /// Transform "Div(x, x)" ==> "1" for integers, if `x` is known to be nonzero.
+    ///
+    /// A division by zero must be kept: it is UB in MIR, and the assertion guarding it panics.
+    fn simplify_div_self(
+        &self,
+        source_info: &SourceInfo,
+        rvalue: &mut Rvalue<'tcx>,
+        nonzero: Option<Local>,
+    ) {
+        if let Rvalue::BinaryOp(BinOp::Div, box (a, b)) = rvalue
+            && let Some(nonzero) = nonzero
+            && let Some(a_place) = a.place()
+            && a_place.as_local() == Some(nonzero)
+            && b.place() == Some(a_place)
+        {
+            let ty = a.ty(self.local_decls, self.tcx);
+            if !ty.is_integral() || !self.should_simplify(source_info, rvalue) {
+                return;
+            }
+
+            let const_ = Const::from_bits(self.tcx, 1, self.param_env.and(ty));
+            let constant = ConstOperand { span: source_info.span, const_, user_ty: None };
+            *rvalue = Rvalue::Use(Operand::Constant(Box::new(constant)));
+        }
+    }
+
+    /// For each block with a single predecessor, finds a local that is known to be nonzero on
+    /// entry: either the predecessor computes `Eq(x, 0)` as its last statement and asserts that it
+    /// is false, or it switches on `x` and only jumps to the block for nonzero values. Borrowed
+    /// locals are never returned, as they may be changed through a pointer.
+    fn nonzero_on_entry(&self, body: &Body<'tcx>) -> IndexVec<BasicBlock, Option<Local>> {
+        let alias_sets = compute_alias_sets(body);
+        let predecessors = body.basic_blocks.predecessors();
+        body.basic_blocks
+            .indices()
+            .map(|bb| {
+                let [pred] = predecessors[bb][..] else { return None };
+                let data = &body.basic_blocks[pred];
+                let local = match data.terminator().kind {
+                    TerminatorKind::Assert { ref cond, expected: false, target, .. }
+                        if target == bb =>
+                    {
+                        let StatementKind::Assign(box (
+                            place,
+                            Rvalue::BinaryOp(BinOp::Eq, box (ref lhs, ref rhs)),
+                        )) = data.statements.last()?.kind
+                        else {
+                            return None;
+                        };
+                        if cond.place() != Some(place) {
+                            return None;
+                        }
+                        let (compared, constant) = match (lhs, rhs) {
+                            (
+                                Operand::Copy(compared) | Operand::Move(compared),
+                                Operand::Constant(constant),
+                            )
+                            | (
+                                Operand::Constant(constant),
+                                Operand::Copy(compared) | Operand::Move(compared),
+                            ) => (compared, constant),
+                            _ => return None,
+                        };
+                        if constant.const_.try_eval_bits(self.tcx, self.param_env) != Some(0) {
+                            return None;
+                        }
+                        compared.as_local()?
+                    }
+                    TerminatorKind::SwitchInt { ref discr, ref targets } => {
+                        let mut arms = targets.iter().filter(|&(_, target)| target == bb);
+                        let is_nonzero = match (arms.next(), arms.next()) {
+                            // `bb` is reached by exactly one value.
+                            (Some((value, _)), None) => value != 0 && targets.otherwise() != bb,
+                            // `bb` is only reached when no value matches.
+                            (None, _) => targets.all_values().contains(&0),
+                            _ => false,
+                        };
+                        if !is_nonzero {
+                            return None;
+                        }
+                        discr.place()?.as_local()?
+                    }
+                    _ => return None,
+                };
+                let place = Place::from(local);
+                (alias_sets.alias_set(place.as_ref()) == AliasSet::Local(local)).then_some(local)
+            })
+            .collect()
+    }
+
+    /// Transform "Op(const c, x)" ==> "Op'(x, const c)" for commutative operations and comparisons,
+    /// so that later passes only need to look for constants on the right.
+    fn canonicalize_binop_operands(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+        let Rvalue::BinaryOp(op, box (lhs, rhs)) = &*rvalue else { return };
+        if lhs.constant().is_none() || rhs.constant().is_some() {
+            return;
+        }
+        let Some(swapped) = op.swap_operands() else { return };
+        if !self.should_simplify(source_info, rvalue) {
+            return;
+        }
+
+        if let Rvalue::BinaryOp(op, box (lhs, rhs)) = rvalue {
+            *op = swapped;
+            std::mem::swap(lhs, rhs);
+        }
+    }
+
     fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
         if let Rvalue::Cast(kind, operand, cast_ty) = rvalue {
             let operand_ty = operand.ty(self.local_decls, self.tcx);
@@ -289,6 +459,49 @@ fn simplify_intrinsic_assert(
             }
         }
     }
+
+    /// A call whose destination has an uninhabited layout can never return. MIR building only
+    /// removes the return edge when the type is visibly uninhabited from the caller, so do it here
+    /// for the remaining cases, like private uninhabited fields or types that only became
+    /// uninhabited after inlining. The blocks only reachable from that edge are removed by the
+    /// next `SimplifyCfg`.
+    fn simplify_uninhabited_call(&self, terminator: &mut Terminator<'tcx>) {
+        let TerminatorKind::Call { destination, target, .. } = &mut terminator.kind else {
+            return;
+        };
+        if target.is_none() {
+            return;
+        }
+        let ty = destination.ty(self.local_decls, self.tcx).ty;
+        // Computing the layout is expensive, so only do it for types that may be uninhabited.
+        if !ty.is_never() && !ty.is_privately_uninhabited(self.tcx, self.param_env) {
+            return;
+        }
+        let layout = self.tcx.layout_of(self.param_env.and(ty));
+        if !layout.is_ok_and(|layout| layout.abi.is_uninhabited()) {
+            return;
+        }
+        if !self.tcx.consider_optimizing(|| {
+            format!(
+                "InstSimplify - Uninhabited call: {:?} SourceInfo: {:?}",
+                destination, terminator.source_info
+            )
+        }) {
+            return;
+        }
+        *target = None;
+    }
+}
+
+/// Returns whether `kind` may change the value of `local`.
+fn writes_local(kind: &StatementKind<'_>, local: Local) -> bool {
+    match *kind {
+        StatementKind::Assign(box (place, _))
+        | StatementKind::SetDiscriminant { box place, .. }
+        | StatementKind::Deinit(box place) => place.local == local,
+        StatementKind::StorageLive(l) | StatementKind::StorageDead(l) => l == local,
+        _ => false,
+    }
 }
 
 fn intrinsic_assert_panics<'tcx>(
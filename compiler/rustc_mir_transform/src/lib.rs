@@ -94,7 +94,9 @@
 mod nrvo;
 mod prettify;
 mod ref_prop;
+mod remove_dominated_asserts;
 mod remove_noop_landing_pads;
+mod remove_redundant_bounds_checks;
 mod remove_storage_markers;
 mod remove_uninit_drops;
 mod remove_unneeded_drops;
@@ -103,6 +105,7 @@
 mod reveal_all;
 mod separate_const_switch;
 mod shim;
+mod sink_cold_blocks;
 mod ssa;
 // This pass is public to allow external drivers to perform MIR cleanup
 mod check_alignment;
@@ -592,6 +595,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &separate_const_switch::SeparateConstSwitch,
             &const_prop::ConstProp,
             &gvn::GVN,
+            &remove_dominated_asserts::RemoveDominatedAsserts,
+            &remove_redundant_bounds_checks::RemoveRedundantBoundsChecks,
             &simplify::SimplifyLocals::AfterGVN,
             &dataflow_const_prop::DataflowConstProp,
             &const_debuginfo::ConstDebugInfo,
@@ -614,6 +619,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             // Cleanup for human readability, off by default.
             &prettify::ReorderBasicBlocks,
             &prettify::ReorderLocals,
+            // Runs after `ReorderBasicBlocks`, which would otherwise undo the block order it picks.
+            &sink_cold_blocks::SinkColdBlocks,
             // Dump the end result for testing and debugging purposes.
             &dump_mir::Marker("PreCodegen"),
         ],
@@ -62,6 +62,23 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     }
 }
 
+/// A pseudo-pass that changes the phase of the body, for the rare pass lists that need to move
+/// the body through a phase before the phase change at the end of `run_passes`.
+///
+/// The body is validated after it like after any other pass, not as for a phase change.
+#[derive(Debug, Clone)]
+pub struct PhaseChange(pub MirPhase);
+
+impl<'tcx> MirPass<'tcx> for PhaseChange {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        change_phase(tcx, body, self.0);
+    }
+
+    fn is_mir_dump_enabled(&self) -> bool {
+        false
+    }
+}
+
 /// Run the sequence of passes without validating the MIR after each pass. The MIR is still
 /// validated at the end.
 pub fn run_passes_no_validate<'tcx>(
@@ -153,20 +170,24 @@ fn run_passes_inner<'tcx>(
     }
 
     if let Some(new_phase) = phase_change {
-        if body.phase >= new_phase {
-            panic!("Invalid MIR phase transition from {:?} to {:?}", body.phase, new_phase);
-        }
-
-        body.phase = new_phase;
-        body.pass_count = 0;
-
-        dump_mir_for_phase_change(tcx, body);
+        change_phase(tcx, body, new_phase);
         if validate || new_phase == MirPhase::Runtime(RuntimePhase::Optimized) {
             validate_body(tcx, body, format!("after phase change to {}", new_phase.name()));
         }
+    }
+}
 
-        body.pass_count = 1;
+fn change_phase<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, new_phase: MirPhase) {
+    if !mir::mir_phase_change_is_valid(body.phase, new_phase) {
+        panic!("Invalid MIR phase transition from {:?} to {:?}", body.phase, new_phase);
     }
+
+    body.phase = new_phase;
+    body.pass_count = 0;
+
+    dump_mir_for_phase_change(tcx, body);
+
+    body.pass_count = 1;
 }
 
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
@@ -85,7 +85,10 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     }
 }
 
-fn permute<I: rustc_index::Idx + Ord, T>(data: &mut IndexVec<I, T>, map: &IndexSlice<I, I>) {
+pub(crate) fn permute<I: rustc_index::Idx + Ord, T>(
+    data: &mut IndexVec<I, T>,
+    map: &IndexSlice<I, I>,
+) {
     // FIXME: It would be nice to have a less-awkward way to apply permutations,
     // but I don't know one that exists.  `sort_by_cached_key` has logic for it
     // internally, but not in a way that we're allowed to use here.
@@ -94,9 +97,9 @@ fn permute<I: rustc_index::Idx + Ord, T>(data: &mut IndexVec<I, T>, map: &IndexS
     *data = enumerated.into_iter().map(|p| p.1).collect();
 }
 
-struct BasicBlockUpdater<'tcx> {
-    map: IndexVec<BasicBlock, BasicBlock>,
-    tcx: TyCtxt<'tcx>,
+pub(crate) struct BasicBlockUpdater<'tcx> {
+    pub map: IndexVec<BasicBlock, BasicBlock>,
+    pub tcx: TyCtxt<'tcx>,
 }
 
 impl<'tcx> MutVisitor<'tcx> for BasicBlockUpdater<'tcx> {
@@ -0,0 +1,202 @@
+//! Removes `Assert` terminators whose condition is already decided by a dominating `SwitchInt`.
+//!
+//! When a block can only be reached through one arm of a `SwitchInt`, the value of the
+//! discriminant is known in every block dominated by that arm: it is equal to the arm's value, or
+//! it is different from all the listed values if the arm is `otherwise`. For instance,
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     switchInt(_2) -> [0: bb2, otherwise: bb1];
+//! }
+//! bb1: {
+//!     _4 = _2;
+//!     _5 = Eq(_4, const 0_u32);
+//!     assert(!move _5, "attempt to divide `{}` by zero", _3) -> [success: bb3, unwind continue];
+//! }
+//! ```
+//!
+//! turns the `assert` into `goto -> bb3`, as `_2` is known not to be `0` in `bb1`.
+//!
+//! This only reasons about SSA locals, so the value that was switched on is guaranteed to be the
+//! value seen by the assertion. A `SwitchInt` on a field of an SSA local, like the one on the
+//! payload of an `Option` in `match d { Some(0) => .., Some(d) => .. }`, also tells the value of
+//! the SSA locals copied out of that field, like the binding of the second arm.
+
+use crate::ssa::SsaLocals;
+use crate::MirPass;
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::IndexVec;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+
+pub struct RemoveDominatedAsserts;
+
+impl<'tcx> MirPass<'tcx> for RemoveDominatedAsserts {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let ssa = SsaLocals::new(body);
+        let comparisons = compute_comparisons(tcx, param_env, &ssa, body);
+        let copied_places = compute_copied_places(&ssa, body);
+
+        let finder = FactFinder {
+            body,
+            ssa: &ssa,
+            dominators: body.basic_blocks.dominators(),
+            comparisons: &comparisons,
+            copied_places: &copied_places,
+        };
+        let mut redundant = Vec::new();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            if let TerminatorKind::Assert { ref cond, expected, target, .. } =
+                data.terminator().kind
+                && let Some(cond) = cond.place().and_then(|place| place.as_local())
+                && finder.eval_bool(cond, bb) == Some(expected)
+            {
+                redundant.push((bb, target));
+            }
+        }
+        debug!(?redundant);
+
+        for (bb, target) in redundant {
+            body.basic_blocks_mut()[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// What is known about the value of a local.
+#[derive(Debug)]
+enum Fact<'a> {
+    /// The local has this value.
+    Is(u128),
+    /// The local has none of these values.
+    IsNone(&'a [u128]),
+}
+
+/// Collects the SSA locals that are defined as `Eq` or `Ne` comparisons of an SSA local with a
+/// constant, together with the comparison operator, the compared local and the constant.
+fn compute_comparisons<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    ssa: &SsaLocals,
+    body: &Body<'tcx>,
+) -> IndexVec<Local, Option<(BinOp, Local, u128)>> {
+    let mut comparisons = IndexVec::from_elem(None, &body.local_decls);
+    for (local, rvalue, _) in ssa.assignments(body) {
+        let Rvalue::BinaryOp(op @ (BinOp::Eq | BinOp::Ne), box (lhs, rhs)) = rvalue else {
+            continue;
+        };
+        let (place, constant) = match (lhs, rhs) {
+            (Operand::Copy(place) | Operand::Move(place), Operand::Constant(constant))
+            | (Operand::Constant(constant), Operand::Copy(place) | Operand::Move(place)) => {
+                (place, constant)
+            }
+            _ => continue,
+        };
+        if let Some(compared) = place.as_local()
+            && ssa.is_ssa(compared)
+            && let Some(bits) = constant.const_.try_eval_bits(tcx, param_env)
+        {
+            comparisons[local] = Some((*op, ssa.copy_classes()[compared], bits));
+        }
+    }
+    comparisons
+}
+
+/// Collects the SSA locals that are defined as a copy of a field of an SSA local. As that local is
+/// never modified, the field has the same value wherever it is read.
+fn compute_copied_places<'tcx>(
+    ssa: &SsaLocals,
+    body: &Body<'tcx>,
+) -> IndexVec<Local, Option<Place<'tcx>>> {
+    let mut copied_places = IndexVec::from_elem(None, &body.local_decls);
+    for (local, rvalue, _) in ssa.assignments(body) {
+        if let Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) = rvalue
+            && !place.projection.is_empty()
+            && place.projection.iter().all(|elem| {
+                matches!(elem, ProjectionElem::Field(..) | ProjectionElem::Downcast(..))
+            })
+            && ssa.is_ssa(place.local)
+        {
+            copied_places[local] = Some(*place);
+        }
+    }
+    copied_places
+}
+
+struct FactFinder<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    ssa: &'a SsaLocals,
+    dominators: &'a Dominators<BasicBlock>,
+    comparisons: &'a IndexVec<Local, Option<(BinOp, Local, u128)>>,
+    copied_places: &'a IndexVec<Local, Option<Place<'tcx>>>,
+}
+
+impl<'a, 'tcx> FactFinder<'a, 'tcx> {
+    /// Tries to decide the value of the boolean local `cond` at the end of `bb`.
+    fn eval_bool(&self, cond: Local, bb: BasicBlock) -> Option<bool> {
+        if !self.ssa.is_ssa(cond) {
+            return None;
+        }
+        let cond = self.ssa.copy_classes()[cond];
+
+        // The condition itself may have been switched on.
+        match self.switch_fact(cond, bb) {
+            Some(Fact::Is(value)) => return Some(value != 0),
+            Some(Fact::IsNone(values)) if values.contains(&0) => return Some(true),
+            Some(Fact::IsNone(values)) if values.contains(&1) => return Some(false),
+            _ => {}
+        }
+
+        // Or it may compare a local that has been switched on with a constant.
+        let (op, compared, constant) = self.comparisons[cond]?;
+        let equal = match self.switch_fact(compared, bb)? {
+            Fact::Is(value) => value == constant,
+            Fact::IsNone(values) if values.contains(&constant) => false,
+            Fact::IsNone(_) => return None,
+        };
+        Some(if op == BinOp::Eq { equal } else { !equal })
+    }
+
+    /// Finds a `SwitchInt` on `local` whose arm dominates `bb`, and returns what that arm tells
+    /// about the value of `local`.
+    fn switch_fact(&self, local: Local, bb: BasicBlock) -> Option<Fact<'a>> {
+        let predecessors = self.body.basic_blocks.predecessors();
+        let mut block = bb;
+        loop {
+            // If `block` has a single predecessor, the edge into it dominates every block that
+            // `block` dominates, so anything known on that edge also holds in `bb`.
+            if let [pred] = predecessors[block][..]
+                && let TerminatorKind::SwitchInt { ref discr, ref targets } =
+                    self.body.basic_blocks[pred].terminator().kind
+                && let Some(discr) = discr.place()
+                && self.holds_value_of(discr, local)
+            {
+                let mut arms = targets.iter().filter(|&(_, target)| target == block);
+                return match (arms.next(), arms.next()) {
+                    // `block` is reached by exactly one value.
+                    (Some((value, _)), None) if targets.otherwise() != block => {
+                        Some(Fact::Is(value))
+                    }
+                    // `block` is only reached when no value matches.
+                    (None, _) => Some(Fact::IsNone(targets.all_values())),
+                    _ => None,
+                };
+            }
+            block = self.dominators.immediate_dominator(block)?;
+        }
+    }
+
+    /// Whether the switched-on `place` always holds the value of the SSA local `local`.
+    fn holds_value_of(&self, place: Place<'tcx>, local: Local) -> bool {
+        match place.as_local() {
+            Some(discr) => self.ssa.is_ssa(discr) && self.ssa.copy_classes()[discr] == local,
+            None => self.copied_places[local] == Some(place),
+        }
+    }
+}
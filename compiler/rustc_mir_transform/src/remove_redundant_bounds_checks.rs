@@ -0,0 +1,718 @@
+//! Removes the bounds checks of indexing operations in loops that are already guarded by the loop
+//! condition.
+//!
+//! The typical examples are
+//!
+//! ```ignore (illustrative)
+//! while i < arr.len() {
+//!     sum += arr[i];
+//!     i += 1;
+//! }
+//!
+//! for i in 0..arr.len() {
+//!     sum += arr[i];
+//! }
+//! ```
+//!
+//! In the first loop, the `switchInt` at the head of the loop only enters the body if `i < N`, so
+//! the `Lt(i, Len(arr))` bounds check of `arr[i]` always succeeds, as long as neither `i` nor `arr`
+//! can be modified in between. Once `Range::<usize>::next` is inlined, the second loop looks the
+//! same, except that the index travels through the `Option` returned by `next`: the comparison of
+//! `start < end` guards the `Some(start)` arm, and the bounds check reads the index back out of
+//! `(_x as Some).0` after matching on the discriminant.
+//!
+//! We find these with a forward dataflow analysis which keeps track of the values of the fields of
+//! locals, and of the pairs of values known to be ordered. Facts about a place are forgotten as soon
+//! as it is written, moved or has its storage changed. Locals whose address escapes are never
+//! tracked, as they may be written through a pointer. Lengths are known for arrays, where they are
+//! part of the type, and for slices behind a pointer that has not been modified since.
+//!
+//! A fact about the field of a variant of an enum only holds while the enum is in that variant, so
+//! these facts are kept apart for each variant. Where the enum is known to be in another variant,
+//! the facts of the variant are the bottom value: every fact holds vacuously. As facts are joined by
+//! intersection, this is what allows them to survive the join of the `Some` and `None` arms above.
+
+use std::iter;
+
+use crate::MirPass;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{
+    MutatingUseContext, NonMutatingUseContext, NonUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+use rustc_mir_dataflow::fmt::DebugWithContext;
+use rustc_mir_dataflow::{
+    Analysis, AnalysisDomain, JoinSemiLattice, MaybeReachable, SwitchIntEdgeEffects,
+};
+use rustc_target::abi::VariantIdx;
+
+pub struct RemoveRedundantBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveRedundantBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let escaping = escaping_locals(body);
+
+        let mut redundant = Vec::new();
+        let analysis = BoundsFacts { tcx, param_env, body, escaping: &escaping };
+        let mut cursor =
+            analysis.into_engine(tcx, body).iterate_to_fixpoint().into_results_cursor(body);
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let TerminatorKind::Assert { ref cond, expected: true, ref msg, target, .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            if !matches!(**msg, AssertKind::BoundsCheck { .. }) {
+                continue;
+            }
+            cursor.seek_before_primary_effect(body.terminator_loc(bb));
+            if let MaybeReachable::Reachable(facts) = cursor.get()
+                && let Some(Value::Lt(lhs, rhs)) = cursor.analysis().operand_value(facts, cond)
+                && facts.contains((lhs, rhs))
+            {
+                redundant.push((bb, target));
+            }
+        }
+        debug!(?redundant);
+
+        for (bb, target) in redundant {
+            body.basic_blocks_mut()[bb].terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// A scalar value that we can reason about.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Atom<'tcx> {
+    /// The current value of a tracked place.
+    Place(Place<'tcx>),
+    /// The length of the slice behind the pointer stored in a tracked place.
+    Len(Place<'tcx>),
+    Const(u128),
+}
+
+impl<'tcx> Atom<'tcx> {
+    fn place(self) -> Option<Place<'tcx>> {
+        match self {
+            Atom::Place(place) | Atom::Len(place) => Some(place),
+            Atom::Const(_) => None,
+        }
+    }
+
+    fn is_clobbered_by(self, written: PlaceRef<'tcx>) -> bool {
+        self.place().is_some_and(|place| overlaps(place.as_ref(), written))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Value<'tcx> {
+    Atom(Atom<'tcx>),
+    /// The result of `Lt(lhs, rhs)`.
+    Lt(Atom<'tcx>, Atom<'tcx>),
+    /// The discriminant of a tracked local.
+    Discriminant(Local),
+}
+
+impl<'tcx> Value<'tcx> {
+    fn is_clobbered_by(self, written: PlaceRef<'tcx>) -> bool {
+        match self {
+            Value::Atom(atom) => atom.is_clobbered_by(written),
+            Value::Lt(lhs, rhs) => lhs.is_clobbered_by(written) || rhs.is_clobbered_by(written),
+            Value::Discriminant(local) => local == written.local,
+        }
+    }
+}
+
+/// Returns whether a write to one of the places may change the value of the other. Places are
+/// only disjoint if they pick different fields of the same value.
+fn overlaps(a: PlaceRef<'_>, b: PlaceRef<'_>) -> bool {
+    a.local == b.local
+        && iter::zip(a.projection, b.projection).find(|(x, y)| x != y).map_or(true, |pair| {
+            !matches!(pair, (ProjectionElem::Field(..), ProjectionElem::Field(..)))
+        })
+}
+
+/// A fact `(lhs, rhs)` states that `lhs < rhs`.
+type Lt<'tcx> = (Atom<'tcx>, Atom<'tcx>);
+
+/// Returns the variant that `fact` depends on: `(_x, V)` if it mentions a place `(_x as V)...`.
+fn guard((lhs, rhs): Lt<'_>) -> Option<(Local, VariantIdx)> {
+    [lhs, rhs].into_iter().filter_map(Atom::place).find_map(|place| {
+        match place.projection.first() {
+            Some(&ProjectionElem::Downcast(_, variant)) => Some((place.local, variant)),
+            _ => None,
+        }
+    })
+}
+
+/// A set of facts known to hold, joined by intersection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LtFacts<'tcx>(FxHashSet<Lt<'tcx>>);
+
+impl JoinSemiLattice for LtFacts<'_> {
+    fn join(&mut self, other: &Self) -> bool {
+        let len = self.0.len();
+        self.0.retain(|fact| other.0.contains(fact));
+        len != self.0.len()
+    }
+}
+
+/// What we know at a given point of the body.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Facts<'tcx> {
+    /// The values of the tracked places that were assigned something we understand.
+    values: FxHashMap<Place<'tcx>, Value<'tcx>>,
+    /// The variants of the tracked enum locals.
+    variants: FxHashMap<Local, VariantIdx>,
+    /// The facts that do not depend on the variant of an enum.
+    lt: LtFacts<'tcx>,
+    /// The facts that depend on a variant, as given by `guard`. They only hold while the local is in
+    /// that variant, and `Unreachable` means that it cannot be, so that every fact holds. A missing
+    /// variant has no facts.
+    guarded: FxHashMap<(Local, VariantIdx), MaybeReachable<LtFacts<'tcx>>>,
+}
+
+impl<'tcx> Facts<'tcx> {
+    /// Returns whether `fact` is known to hold, assuming the variant it depends on.
+    fn contains(&self, fact: Lt<'tcx>) -> bool {
+        let Some(guard) = guard(fact) else { return self.lt.0.contains(&fact) };
+        match self.guarded.get(&guard) {
+            None => false,
+            Some(MaybeReachable::Unreachable) => true,
+            Some(MaybeReachable::Reachable(facts)) => facts.0.contains(&fact),
+        }
+    }
+
+    fn insert(&mut self, fact: Lt<'tcx>) {
+        let facts = match guard(fact) {
+            None => &mut self.lt,
+            Some(guard) => match self
+                .guarded
+                .entry(guard)
+                .or_insert(MaybeReachable::Reachable(LtFacts::default()))
+            {
+                MaybeReachable::Unreachable => return,
+                MaybeReachable::Reachable(facts) => facts,
+            },
+        };
+        facts.0.insert(fact);
+    }
+
+    /// Returns the facts, including the ones that only hold in another variant than the current one.
+    fn iter(&self) -> impl Iterator<Item = Lt<'tcx>> + '_ {
+        let guarded = self.guarded.values().filter_map(|facts| match facts {
+            MaybeReachable::Reachable(facts) => Some(facts),
+            MaybeReachable::Unreachable => None,
+        });
+        iter::once(&self.lt).chain(guarded).flat_map(|facts| facts.0.iter().copied())
+    }
+
+    /// Adds the facts established by an assignment, once the places it writes have been forgotten.
+    fn extend(&mut self, assigned: Facts<'tcx>) {
+        self.values.extend(assigned.values);
+        self.variants.extend(assigned.variants);
+        for (guard, facts) in &assigned.guarded {
+            if let MaybeReachable::Unreachable = facts {
+                self.guarded.insert(*guard, MaybeReachable::Unreachable);
+            }
+        }
+        for fact in assigned.iter() {
+            self.insert(fact);
+        }
+    }
+
+    /// Forgets everything we know about the `written` places.
+    fn forget(&mut self, written: &[PlaceRef<'tcx>]) {
+        for &place in written {
+            let intact =
+                |&(lhs, rhs): &Lt<'tcx>| !lhs.is_clobbered_by(place) && !rhs.is_clobbered_by(place);
+            self.values.retain(|key, value| {
+                !overlaps(key.as_ref(), place) && !value.is_clobbered_by(place)
+            });
+            self.lt.0.retain(intact);
+            // The local may not be in the same variant anymore.
+            self.guarded.retain(|&(local, _), _| local != place.local);
+            for facts in self.guarded.values_mut() {
+                if let MaybeReachable::Reachable(facts) = facts {
+                    facts.0.retain(intact);
+                }
+            }
+            self.variants.remove(&place.local);
+        }
+    }
+}
+
+impl JoinSemiLattice for Facts<'_> {
+    fn join(&mut self, other: &Self) -> bool {
+        let (values, variants) = (self.values.len(), self.variants.len());
+        self.values.retain(|place, value| other.values.get(place) == Some(value));
+        self.variants.retain(|local, variant| other.variants.get(local) == Some(variant));
+        let mut changed = self.lt.join(&other.lt)
+            || values != self.values.len()
+            || variants != self.variants.len();
+
+        // A variant missing on one side has no facts, and neither does the join.
+        self.guarded.retain(|guard, facts| {
+            let Some(other) = other.guarded.get(guard) else {
+                changed |= *facts != MaybeReachable::Reachable(LtFacts::default());
+                return false;
+            };
+            changed |= facts.join(other);
+            true
+        });
+        changed
+    }
+}
+
+impl<C> DebugWithContext<C> for Facts<'_> {}
+
+struct BoundsFacts<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    escaping: &'a BitSet<Local>,
+}
+
+impl<'tcx> AnalysisDomain<'tcx> for BoundsFacts<'_, 'tcx> {
+    type Domain = MaybeReachable<Facts<'tcx>>;
+
+    const NAME: &'static str = "bounds_facts";
+
+    fn bottom_value(&self, _: &Body<'tcx>) -> Self::Domain {
+        MaybeReachable::Unreachable
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, state: &mut Self::Domain) {
+        *state = MaybeReachable::Reachable(Facts::default());
+    }
+}
+
+impl<'tcx> Analysis<'tcx> for BoundsFacts<'_, 'tcx> {
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement<'tcx>,
+        location: Location,
+    ) {
+        let MaybeReachable::Reachable(facts) = state else { return };
+        let written = written_places(|visitor| visitor.visit_statement(statement, location));
+        let mut assigned = Assigned { written: &written, facts: Facts::default() };
+        if let StatementKind::Assign(box (dest, ref rvalue)) = statement.kind
+            && let Some(dest) = self.tracked_place(dest.as_ref())
+        {
+            self.assign(facts, dest, rvalue, &mut assigned);
+        }
+        facts.forget(&written);
+        facts.extend(assigned.facts);
+    }
+
+    fn apply_terminator_effect<'mir>(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        location: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        // The discriminant of a `switchInt` is only forgotten by the edge effects, which need it.
+        if let MaybeReachable::Reachable(facts) = state
+            && !matches!(terminator.kind, TerminatorKind::SwitchInt { .. })
+        {
+            facts.forget(&written_places(|visitor| visitor.visit_terminator(terminator, location)));
+        }
+        terminator.edges()
+    }
+
+    fn apply_call_return_effect(
+        &mut self,
+        _: &mut Self::Domain,
+        _: BasicBlock,
+        _: CallReturnPlaces<'_, 'tcx>,
+    ) {
+        // The return places were already forgotten by the terminator effect.
+    }
+
+    fn apply_switch_int_edge_effects(
+        &mut self,
+        block: BasicBlock,
+        discr: &Operand<'tcx>,
+        apply_edge_effects: &mut impl SwitchIntEdgeEffects<Self::Domain>,
+    ) {
+        let TerminatorKind::SwitchInt { ref targets, .. } =
+            self.body.basic_blocks[block].terminator().kind
+        else {
+            bug!("`apply_switch_int_edge_effects` called on a non-`switchInt` terminator")
+        };
+        // The `otherwise` edge of a `switchInt` on a `bool` with a single `0` arm is its `true` edge.
+        let otherwise = (targets.all_values() == [0]).then_some(1);
+        let written = written_places(|visitor| visitor.visit_operand(discr, Location::START));
+
+        apply_edge_effects.apply(|state, target| {
+            let MaybeReachable::Reachable(facts) = state else { return };
+            match self.operand_value(facts, discr) {
+                Some(Value::Lt(lhs, rhs)) if target.value.or(otherwise) == Some(1) => {
+                    facts.insert((lhs, rhs));
+                }
+                Some(Value::Discriminant(local)) => {
+                    if let Some(value) = target.value
+                        && let Some(variant) = self.variant_with_discriminant(local, value)
+                    {
+                        self.set_variant(facts, local, variant);
+                    }
+                }
+                _ => {}
+            }
+            facts.forget(&written);
+        });
+    }
+}
+
+/// The facts established by an assignment. They only hold once the places written by the statement
+/// have been forgotten, and must not mention any of them, except for the parts of the destination.
+struct Assigned<'a, 'tcx> {
+    written: &'a [PlaceRef<'tcx>],
+    facts: Facts<'tcx>,
+}
+
+impl<'tcx> Assigned<'_, 'tcx> {
+    fn is_intact(&self, atom: Atom<'tcx>) -> bool {
+        !self.written.iter().any(|&written| atom.is_clobbered_by(written))
+    }
+
+    fn insert_value(&mut self, place: Place<'tcx>, value: Value<'tcx>) {
+        if !self.written.iter().any(|&written| value.is_clobbered_by(written)) {
+            self.facts.values.insert(place, value);
+        }
+    }
+}
+
+impl<'tcx> BoundsFacts<'_, 'tcx> {
+    /// Returns the canonical form of `place` if we track its value: it must be made of fields and
+    /// variants of a local whose address does not escape, and must not go through a union.
+    /// Variants are only tracked for locals, so a downcast may only come first.
+    fn tracked_place(&self, place: PlaceRef<'tcx>) -> Option<Place<'tcx>> {
+        if self.escaping.contains(place.local) {
+            return None;
+        }
+        let mut projection = Vec::with_capacity(place.projection.len());
+        for (base, elem) in place.iter_projections() {
+            match elem {
+                ProjectionElem::Field(..) if base.ty(self.body, self.tcx).ty.is_union() => {
+                    return None;
+                }
+                ProjectionElem::Field(..) => projection.push(elem),
+                ProjectionElem::Downcast(_, variant) if base.projection.is_empty() => {
+                    projection.push(ProjectionElem::Downcast(None, variant))
+                }
+                _ => return None,
+            }
+        }
+        Some(Place { local: place.local, projection: self.tcx.mk_place_elems(&projection) })
+    }
+
+    /// Like `tracked_place`, but also requires the variant that `place` reads from to be known.
+    fn read_place(&self, facts: &Facts<'tcx>, place: PlaceRef<'tcx>) -> Option<Place<'tcx>> {
+        let place = self.tracked_place(place)?;
+        if let Some(&ProjectionElem::Downcast(_, variant)) = place.projection.first()
+            && facts.variants.get(&place.local) != Some(&variant)
+        {
+            return None;
+        }
+        Some(place)
+    }
+
+    fn place_value(&self, facts: &Facts<'tcx>, place: PlaceRef<'tcx>) -> Option<Value<'tcx>> {
+        let place = self.read_place(facts, place)?;
+        Some(facts.values.get(&place).copied().unwrap_or(Value::Atom(Atom::Place(place))))
+    }
+
+    fn operand_value(&self, facts: &Facts<'tcx>, operand: &Operand<'tcx>) -> Option<Value<'tcx>> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.place_value(facts, place.as_ref()),
+            Operand::Constant(constant) => {
+                let bits = constant.const_.try_eval_bits(self.tcx, self.param_env)?;
+                Some(Value::Atom(Atom::Const(bits)))
+            }
+        }
+    }
+
+    fn operand_atom(&self, facts: &Facts<'tcx>, operand: &Operand<'tcx>) -> Option<Atom<'tcx>> {
+        match self.operand_value(facts, operand)? {
+            Value::Atom(atom) => Some(atom),
+            Value::Lt(..) | Value::Discriminant(_) => None,
+        }
+    }
+
+    fn variant_with_discriminant(&self, local: Local, value: u128) -> Option<VariantIdx> {
+        let ty::Adt(adt, _) = self.body.local_decls[local].ty.kind() else { return None };
+        if !adt.is_enum() {
+            return None;
+        }
+        adt.discriminants(self.tcx)
+            .find(|(_, discr)| discr.val == value)
+            .map(|(variant, _)| variant)
+    }
+
+    /// Records that `local` is in `variant`, so that the facts about its other variants hold.
+    fn set_variant(&self, facts: &mut Facts<'tcx>, local: Local, variant: VariantIdx) {
+        let ty::Adt(adt, _) = self.body.local_decls[local].ty.kind() else {
+            bug!("the variant of a non-ADT local {local:?} is known")
+        };
+        for other in adt.variants().indices().filter(|&other| other != variant) {
+            facts.guarded.insert((local, other), MaybeReachable::Unreachable);
+        }
+        facts.variants.insert(local, variant);
+    }
+
+    fn assign(
+        &self,
+        facts: &Facts<'tcx>,
+        dest: Place<'tcx>,
+        rvalue: &Rvalue<'tcx>,
+        assigned: &mut Assigned<'_, 'tcx>,
+    ) {
+        match rvalue {
+            Rvalue::Use(operand) => self.copy_operand(facts, dest, operand, assigned),
+            Rvalue::CopyForDeref(place) => self.copy_place(facts, dest, place.as_ref(), assigned),
+            // Reborrowing `*p` copies the address and metadata of `p`.
+            Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) => {
+                if let Some((pointer, ProjectionElem::Deref)) = place.last_projection()
+                    && let Some(Value::Atom(atom)) = self.place_value(facts, pointer)
+                {
+                    assigned.insert_value(dest, Value::Atom(atom));
+                }
+            }
+            Rvalue::Len(place) => {
+                if let Some(len) = self.len(facts, *place) {
+                    assigned.insert_value(dest, Value::Atom(len));
+                }
+            }
+            // Only `usize` comparisons are unsigned and can imply a bounds check.
+            Rvalue::BinaryOp(op @ (BinOp::Lt | BinOp::Gt), box (lhs, rhs))
+                if lhs.ty(self.body, self.tcx) == self.tcx.types.usize =>
+            {
+                let (lhs, rhs) = if *op == BinOp::Lt { (lhs, rhs) } else { (rhs, lhs) };
+                if let Some(lhs) = self.operand_atom(facts, lhs)
+                    && let Some(rhs) = self.operand_atom(facts, rhs)
+                {
+                    assigned.insert_value(dest, Value::Lt(lhs, rhs));
+                }
+            }
+            Rvalue::Discriminant(place) => {
+                if let Some(local) = place.as_local()
+                    && !self.escaping.contains(local)
+                {
+                    assigned.insert_value(dest, Value::Discriminant(local));
+                }
+            }
+            Rvalue::Aggregate(box kind, operands) => {
+                let base = match *kind {
+                    AggregateKind::Tuple => dest,
+                    AggregateKind::Adt(def_id, variant, _, _, None) => {
+                        if !self.tcx.adt_def(def_id).is_enum() {
+                            dest
+                        } else if let Some(local) = dest.as_local() {
+                            self.set_variant(&mut assigned.facts, local, variant);
+                            self.tcx.mk_place_downcast_unnamed(dest, variant)
+                        } else {
+                            return;
+                        }
+                    }
+                    _ => return,
+                };
+                for (field, operand) in operands.iter_enumerated() {
+                    let ty = operand.ty(self.body, self.tcx);
+                    let dest = self.tcx.mk_place_field(base, field, ty);
+                    self.copy_operand(facts, dest, operand, assigned);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn copy_operand(
+        &self,
+        facts: &Facts<'tcx>,
+        dest: Place<'tcx>,
+        operand: &Operand<'tcx>,
+        assigned: &mut Assigned<'_, 'tcx>,
+    ) {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => {
+                self.copy_place(facts, dest, place.as_ref(), assigned)
+            }
+            Operand::Constant(_) => {
+                if let Some(atom) = self.operand_atom(facts, operand) {
+                    assigned.insert_value(dest, Value::Atom(atom));
+                }
+            }
+        }
+    }
+
+    /// Records that `dest` now holds the value `src` had, along with everything we knew about its
+    /// parts.
+    fn copy_place(
+        &self,
+        facts: &Facts<'tcx>,
+        dest: Place<'tcx>,
+        src: PlaceRef<'tcx>,
+        assigned: &mut Assigned<'_, 'tcx>,
+    ) {
+        let Some(src) = self.read_place(facts, src) else { return };
+        // Moves a place under `src` to the same place under `dest`.
+        let rebase = |place: Place<'tcx>| {
+            if place.local != src.local {
+                return None;
+            }
+            let rest = place.projection.strip_prefix(&src.projection[..])?;
+            self.tracked_place(dest.project_deeper(rest, self.tcx).as_ref())
+        };
+
+        assigned.insert_value(
+            dest,
+            facts.values.get(&src).copied().unwrap_or(Value::Atom(Atom::Place(src))),
+        );
+        for (&place, &value) in &facts.values {
+            if let Some(place) = rebase(place) {
+                assigned.insert_value(place, value);
+            }
+        }
+        let rebase_atom = |atom: Atom<'tcx>| match atom {
+            Atom::Place(place) => rebase(place).map(Atom::Place),
+            Atom::Len(place) => rebase(place).map(Atom::Len),
+            Atom::Const(_) => None,
+        };
+        for (lhs, rhs) in facts.iter() {
+            let fact = match (rebase_atom(lhs), rebase_atom(rhs)) {
+                (None, None) => continue,
+                (Some(lhs), Some(rhs)) => (lhs, rhs),
+                (Some(lhs), None) if assigned.is_intact(rhs) => (lhs, rhs),
+                (None, Some(rhs)) if assigned.is_intact(lhs) => (lhs, rhs),
+                _ => continue,
+            };
+            assigned.facts.insert(fact);
+        }
+        if let (Some(dest), Some(src)) = (dest.as_local(), src.as_local())
+            && let Some(&variant) = facts.variants.get(&src)
+        {
+            self.set_variant(&mut assigned.facts, dest, variant);
+        }
+    }
+
+    fn len(&self, facts: &Facts<'tcx>, place: Place<'tcx>) -> Option<Atom<'tcx>> {
+        if let ty::Array(_, len) = place.ty(self.body, self.tcx).ty.kind() {
+            let len = len.try_eval_target_usize(self.tcx, self.param_env)?;
+            return Some(Atom::Const(len.into()));
+        }
+        let (pointer, ProjectionElem::Deref) = place.as_ref().last_projection()? else {
+            return None;
+        };
+        match self.place_value(facts, pointer)? {
+            Value::Atom(Atom::Place(pointer)) => Some(Atom::Len(pointer)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the places whose value may change when `visit` visits: those that are written, moved
+/// or have their storage changed. Writes through a pointer are left out, as they can only reach
+/// escaping locals, which are never tracked.
+fn written_places<'tcx>(visit: impl FnOnce(&mut WrittenPlaces<'tcx>)) -> Vec<PlaceRef<'tcx>> {
+    let mut visitor = WrittenPlaces(Vec::new());
+    visit(&mut visitor);
+    visitor.0
+}
+
+struct WrittenPlaces<'tcx>(Vec<PlaceRef<'tcx>>);
+
+impl<'tcx> Visitor<'tcx> for WrittenPlaces<'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+        let written = match context {
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Borrow | MutatingUseContext::AddressOf,
+            ) => false,
+            PlaceContext::MutatingUse(_)
+            | PlaceContext::NonMutatingUse(NonMutatingUseContext::Move) => true,
+            _ => false,
+        };
+        if written && !place.is_indirect() {
+            // Anything other than fields and variants may be any part of its base.
+            let len = place
+                .projection
+                .iter()
+                .position(|elem| {
+                    !matches!(elem, ProjectionElem::Field(..) | ProjectionElem::Downcast(..))
+                })
+                .unwrap_or(place.projection.len());
+            self.0.push(place.as_ref().truncate(len));
+        }
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) =
+            context
+        {
+            self.0.push(PlaceRef { local, projection: &[] });
+        }
+    }
+}
+
+/// Returns the locals whose address may be used to read or write them behind our back: those that
+/// are dropped in place, and those borrowed into a place that is ever read. A borrow that is never
+/// read, like the ones left behind for debuginfo by inlining, cannot be written through.
+fn escaping_locals(body: &Body<'_>) -> BitSet<Local> {
+    let mut visitor = EscapingLocals {
+        read: BitSet::new_empty(body.local_decls.len()),
+        borrows: Vec::new(),
+        escaping: BitSet::new_empty(body.local_decls.len()),
+    };
+    visitor.visit_body(body);
+    for (dest, local) in visitor.borrows {
+        if dest.as_local().map_or(true, |dest| visitor.read.contains(dest)) {
+            visitor.escaping.insert(local);
+        }
+    }
+    visitor.escaping
+}
+
+struct EscapingLocals<'tcx> {
+    read: BitSet<Local>,
+    /// The destinations of the borrows of locals, along with the borrowed local.
+    borrows: Vec<(Place<'tcx>, Local)>,
+    escaping: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for EscapingLocals<'tcx> {
+    fn visit_assign(&mut self, dest: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        match rvalue {
+            Rvalue::Ref(_, BorrowKind::Fake, _) => {}
+            Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) if !place.is_indirect() => {
+                self.borrows.push((*dest, place.local));
+            }
+            _ => {}
+        }
+        self.super_assign(dest, rvalue, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Drop { place, .. } = terminator.kind
+            && !place.is_indirect()
+        {
+            self.escaping.insert(place.local);
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_use() && !context.is_place_assignment() {
+            self.read.insert(local);
+        }
+    }
+}
@@ -113,6 +113,8 @@ fn make_shim<'tcx>(tcx: TyCtxt<'tcx>, instance: ty::InstanceDef<'tcx>) -> Body<'
             &simplify::SimplifyCfg::MakeShim,
             &abort_unwinding_calls::AbortUnwindingCalls,
             &add_call_guards::CriticalCallEdges,
+            // Shims have no constants to promote, but must still go through that phase.
+            &pm::PhaseChange(CONSTS_PROMOTED),
         ],
         Some(MirPhase::Runtime(RuntimePhase::Optimized)),
     );
@@ -0,0 +1,109 @@
+//! Moves the basic blocks that are only reached through cold code, or that can only lead to cold
+//! code, to the end of the body.
+//!
+//! A block is considered cold if it is a cleanup block, if it is unreachable, if it calls a
+//! `#[cold]` function (which includes the panic machinery), if all of its successors are cold,
+//! or if it is dominated by a cold block, like the code running after a cold call returns.
+//! Sinking those blocks keeps the hot path dense for backends that lay out blocks in MIR order.
+
+use crate::prettify::{permute, BasicBlockUpdater};
+use crate::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::visit::MutVisitor;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_target::spec::abi::Abi;
+
+pub struct SinkColdBlocks;
+
+impl<'tcx> MirPass<'tcx> for SinkColdBlocks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let cold = cold_blocks(tcx, body);
+        // Never move the start block, and don't bother if there is nothing to sink.
+        if cold.contains(START_BLOCK) || cold.is_empty() {
+            return;
+        }
+
+        let (hot, cold): (Vec<_>, Vec<_>) =
+            body.basic_blocks.indices().partition(|&bb| !cold.contains(bb));
+        let order: IndexVec<BasicBlock, BasicBlock> = hot.into_iter().chain(cold).collect();
+        if order.iter().is_sorted() {
+            return;
+        }
+
+        let mut updater = BasicBlockUpdater { map: order.invert_bijective_mapping(), tcx };
+        debug_assert_eq!(updater.map[START_BLOCK], START_BLOCK);
+        updater.visit_body(body);
+
+        permute(body.basic_blocks.as_mut(), &updater.map);
+    }
+}
+
+/// Computes the set of blocks that are only reached through cold code, or from which every path
+/// leads to cold code.
+fn cold_blocks<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> BitSet<BasicBlock> {
+    let mut cold = BitSet::new_empty(body.basic_blocks.len());
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        if data.is_cleanup || is_cold_terminator(tcx, body, data.terminator()) {
+            cold.insert(bb);
+        }
+    }
+
+    // Propagate coldness backwards to the blocks whose successors are all cold, and forwards to
+    // the blocks that are dominated by a cold block, until a fixpoint is reached. Visiting blocks
+    // in postorder and in reverse postorder respectively means that this usually converges after
+    // a single iteration.
+    let dominators = body.basic_blocks.dominators();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &bb in body.basic_blocks.reverse_postorder().iter().rev() {
+            if cold.contains(bb) {
+                continue;
+            }
+            let mut successors = body.basic_blocks[bb].terminator().successors().peekable();
+            if successors.peek().is_some() && successors.all(|succ| cold.contains(succ)) {
+                cold.insert(bb);
+                changed = true;
+            }
+        }
+        for &bb in body.basic_blocks.reverse_postorder() {
+            if !cold.contains(bb)
+                && let Some(dominator) = dominators.immediate_dominator(bb)
+                && cold.contains(dominator)
+            {
+                cold.insert(bb);
+                changed = true;
+            }
+        }
+    }
+    cold
+}
+
+fn is_cold_terminator<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    terminator: &Terminator<'tcx>,
+) -> bool {
+    match terminator.kind {
+        TerminatorKind::Unreachable => true,
+        TerminatorKind::Call { ref func, .. } => {
+            let func_ty = func.ty(body, tcx);
+            match *func_ty.kind() {
+                ty::FnDef(def_id, _) => {
+                    func_ty.fn_sig(tcx).abi() == Abi::RustCold
+                        || tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::COLD)
+                }
+                ty::FnPtr(sig) => sig.abi() == Abi::RustCold,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
@@ -812,7 +812,8 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
         };
 
         match terminator.kind {
-            mir::TerminatorKind::Call { ref func, ref args, .. } => {
+            mir::TerminatorKind::Call { ref func, ref args, .. }
+            | mir::TerminatorKind::TailCall { ref func, ref args, .. } => {
                 let callee_ty = func.ty(self.body, tcx);
                 let callee_ty = self.monomorphize(callee_ty);
                 self.check_fn_args_move_size(callee_ty, args, location);
@@ -866,7 +867,10 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
             push_mono_lang_item(self, reason.lang_item());
         }
 
-        self.visiting_call_terminator = matches!(terminator.kind, mir::TerminatorKind::Call { .. });
+        self.visiting_call_terminator = matches!(
+            terminator.kind,
+            mir::TerminatorKind::Call { .. } | mir::TerminatorKind::TailCall { .. }
+        );
         self.super_terminator(terminator, location);
         self.visiting_call_terminator = false;
     }
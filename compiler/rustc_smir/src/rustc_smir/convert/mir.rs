@@ -255,6 +255,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
             OffsetOf(indices) => stable_mir::mir::NullOp::OffsetOf(
                 indices.iter().map(|idx| idx.stable(tables)).collect(),
             ),
+            UbChecks => stable_mir::mir::NullOp::UbChecks,
         }
     }
 }
@@ -290,6 +291,9 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
             }
             ForGuardBinding => stable_mir::mir::FakeReadCause::ForGuardBinding,
             ForLet(local_def_id) => stable_mir::mir::FakeReadCause::ForLet(opaque(local_def_id)),
+            ForLetElse(local_def_id) => {
+                stable_mir::mir::FakeReadCause::ForLetElse(opaque(local_def_id))
+            }
             ForIndex => stable_mir::mir::FakeReadCause::ForIndex,
         }
     }
@@ -536,6 +540,9 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     movability.stable(tables),
                 )
             }
+            mir::AggregateKind::RawPtr(ty, mutability) => {
+                stable_mir::mir::AggregateKind::RawPtr(ty.stable(tables), mutability.stable(tables))
+            }
         }
     }
 }
@@ -638,6 +645,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 unwind: unwind.stable(tables),
             },
             mir::TerminatorKind::Yield { .. }
+            | mir::TerminatorKind::TailCall { .. }
             | mir::TerminatorKind::CoroutineDrop
             | mir::TerminatorKind::FalseEdge { .. }
             | mir::TerminatorKind::FalseUnwind { .. } => unreachable!(),
@@ -1043,6 +1043,7 @@
         mir_make_place,
         mir_move,
         mir_offset,
+        mir_raw_ptr,
         mir_retag,
         mir_return,
         mir_set_discriminant,
@@ -1050,6 +1051,8 @@
         mir_static_mut,
         mir_storage_dead,
         mir_storage_live,
+        mir_tail_call,
+        mir_ub_checks,
         mir_unreachable,
         mir_unwind_cleanup,
         mir_unwind_continue,
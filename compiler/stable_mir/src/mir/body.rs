@@ -244,6 +244,7 @@ pub enum FakeReadCause {
     ForMatchedPlace(LocalDefId),
     ForGuardBinding,
     ForLet(LocalDefId),
+    ForLetElse(LocalDefId),
     ForIndex,
 }
 
@@ -419,6 +420,7 @@ pub enum AggregateKind {
     Adt(AdtDef, VariantIdx, GenericArgs, Option<UserTypeAnnotationIndex>, Option<FieldIdx>),
     Closure(ClosureDef, GenericArgs),
     Coroutine(CoroutineDef, GenericArgs, Movability),
+    RawPtr(Ty, Mutability),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -694,6 +696,8 @@ pub enum NullOp {
     AlignOf,
     /// Returns the offset of a field.
     OffsetOf(Vec<(VariantIdx, FieldIdx)>),
+    /// Returns whether UB checks are enabled.
+    UbChecks,
 }
 
 impl Operand {
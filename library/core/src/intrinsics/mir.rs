@@ -234,6 +234,8 @@
 //!  - Unary and binary operations use their normal Rust syntax - `a * b`, `!c`, etc.
 //!  - The binary operation `Offset` can be created via [`Offset`].
 //!  - Checked binary operations are represented by wrapping the associated binop in [`Checked`].
+//!  - Raw pointers can be built from a data pointer and metadata via [`RawPtr`].
+//!  - Whether runtime UB checks are enabled can be read with [`UbChecks`].
 //!  - Array repetition syntax (`[foo; 10]`) creates the associated rvalue.
 //!
 //! #### Terminators
@@ -244,6 +246,8 @@
 //!       otherwise branch.
 //!  - [`Call`] has an associated function as well. The third argument of this function is a normal
 //!    function call expression, for example `my_other_function(a, 5)`.
+//!  - [`TailCall`] takes just the function call expression, since it has no destination and no
+//!    successors.
 //!
 
 #![unstable(
@@ -316,6 +320,10 @@ fn UnwindCleanup(goto: BasicBlock)
 define!("mir_unreachable", fn Unreachable() -> BasicBlock);
 define!("mir_drop", fn Drop<T, U>(place: T, goto: BasicBlock, unwind_action: U));
 define!("mir_call", fn Call<U>(call: (), goto: BasicBlock, unwind_action: U));
+define!("mir_tail_call",
+    /// A call that replaces the current stack frame.
+    fn TailCall<T>(call: T)
+);
 define!("mir_unwind_resume",
     /// A terminator that resumes the unwinding.
     fn UnwindResume()
@@ -338,6 +346,18 @@ fn Discriminant<T>(place: T) -> <T as ::core::marker::DiscriminantKind>::Discrim
 );
 define!("mir_set_discriminant", fn SetDiscriminant<T>(place: T, index: u32));
 define!("mir_offset", fn Offset<T, U>(ptr: T, count: U) -> T);
+define!(
+    "mir_raw_ptr",
+    /// Builds a raw pointer of type `P` from a data pointer and its metadata.
+    ///
+    /// For thin pointers, `meta` must be `()`.
+    fn RawPtr<P, D, M>(data: D, meta: M) -> P
+);
+define!(
+    "mir_ub_checks",
+    /// Returns whether runtime UB checks are enabled in the crate being codegened.
+    fn UbChecks() -> bool
+);
 define!(
     "mir_field",
     /// Access the field with the given index of some place.
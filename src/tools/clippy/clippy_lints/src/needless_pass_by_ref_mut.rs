@@ -471,7 +471,7 @@ fn fake_read(
             ..
         } = &cmt.place
         {
-            if let FakeReadCause::ForLet(Some(inner)) = cause {
+            if let FakeReadCause::ForLet(Some(inner)) | FakeReadCause::ForLetElse(Some(inner)) = cause {
                 // Seems like we are inside an async function. We need to store the closure `DefId`
                 // to go through it afterwards.
                 self.async_closures.insert(inner);
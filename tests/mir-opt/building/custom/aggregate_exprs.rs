@@ -65,8 +65,20 @@ fn adt() -> Onion {
     )
 }
 
+// EMIT_MIR aggregate_exprs.raw_ptr.built.after.mir
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+fn raw_ptr(data: *const i32, len: usize) -> *const [i32] {
+    mir!(
+        {
+            RET = RawPtr(data, len);
+            Return()
+        }
+    )
+}
+
 fn main() {
     assert_eq!(tuple(), (1, true));
     assert_eq!(array(), [1, 2]);
     assert_eq!(unsafe { adt().neon }, 1);
+    assert_eq!(unsafe { &*raw_ptr([1, 2, 3].as_ptr(), 2) }, &[1, 2]);
 }
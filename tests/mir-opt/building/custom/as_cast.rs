@@ -26,6 +26,28 @@ fn float_to_int(x: f32) -> i32 {
     )
 }
 
+// EMIT_MIR as_cast.int_to_float.built.after.mir
+#[custom_mir(dialect = "built")]
+fn int_to_float(x: i32) -> f64 {
+    mir!(
+        {
+            RET = x as f64;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR as_cast.float_to_float.built.after.mir
+#[custom_mir(dialect = "built")]
+fn float_to_float(x: f32) -> f64 {
+    mir!(
+        {
+            RET = x as f64;
+            Return()
+        }
+    )
+}
+
 // EMIT_MIR as_cast.int_to_ptr.built.after.mir
 #[custom_mir(dialect = "built")]
 fn int_to_ptr(x: usize) -> *const i32 {
@@ -40,5 +62,7 @@ fn int_to_ptr(x: usize) -> *const i32 {
 fn main() {
     assert_eq!(int_to_int(5), 5);
     assert_eq!(float_to_int(5.), 5);
+    assert_eq!(int_to_float(5), 5.);
+    assert_eq!(float_to_float(5.), 5.);
     assert_eq!(int_to_ptr(0), std::ptr::null());
 }
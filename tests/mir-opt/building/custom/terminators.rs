@@ -36,6 +36,16 @@ fn indirect_call(x: i32, f: fn(i32) -> i32) -> i32 {
     )
 }
 
+// EMIT_MIR terminators.tail_call.built.after.mir
+#[custom_mir(dialect = "built")]
+fn tail_call(x: i32) -> i32 {
+    mir!(
+        {
+            TailCall(ident(x))
+        }
+    )
+}
+
 struct WriteOnDrop<'a>(&'a mut i32, i32);
 
 impl<'a> Drop for WriteOnDrop<'a> {
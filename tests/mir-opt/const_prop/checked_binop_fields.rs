@@ -0,0 +1,77 @@
+// unit-test: ConstProp
+// Check that reading either field of a constant `CheckedBinaryOp` yields the folded value or the
+// overflow flag.
+#![feature(custom_mir, core_intrinsics)]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR checked_binop_fields.add_value.ConstProp.diff
+#[custom_mir(dialect = "analysis")]
+fn add_value() -> u8 {
+    // CHECK-LABEL: fn add_value(
+    // CHECK: [[checked:_.*]] = const (3_u8, false);
+    // CHECK: _0 = const 3_u8;
+    mir!(
+        let checked: (u8, bool);
+        {
+            checked = Checked(1_u8 + 2_u8);
+            RET = checked.0;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR checked_binop_fields.add_overflow.ConstProp.diff
+#[custom_mir(dialect = "analysis")]
+fn add_overflow() -> bool {
+    // CHECK-LABEL: fn add_overflow(
+    // CHECK: [[checked:_.*]] = const (3_u8, false);
+    // CHECK: _0 = const false;
+    mir!(
+        let checked: (u8, bool);
+        {
+            checked = Checked(1_u8 + 2_u8);
+            RET = checked.1;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR checked_binop_fields.overflowing_add_value.ConstProp.diff
+#[custom_mir(dialect = "analysis")]
+fn overflowing_add_value() -> u8 {
+    // CHECK-LABEL: fn overflowing_add_value(
+    // CHECK: [[checked:_.*]] = const (0_u8, true);
+    // CHECK: _0 = const 0_u8;
+    mir!(
+        let checked: (u8, bool);
+        {
+            checked = Checked(255_u8 + 1_u8);
+            RET = checked.0;
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR checked_binop_fields.overflowing_add_overflow.ConstProp.diff
+#[custom_mir(dialect = "analysis")]
+fn overflowing_add_overflow() -> bool {
+    // CHECK-LABEL: fn overflowing_add_overflow(
+    // CHECK: [[checked:_.*]] = const (0_u8, true);
+    // CHECK: _0 = const true;
+    mir!(
+        let checked: (u8, bool);
+        {
+            checked = Checked(255_u8 + 1_u8);
+            RET = checked.1;
+            Return()
+        }
+    )
+}
+
+fn main() {
+    add_value();
+    add_overflow();
+    overflowing_add_value();
+    overflowing_add_overflow();
+}
@@ -0,0 +1,12 @@
+// skip-filecheck
+// unit-test: ConstProp
+// EMIT_MIR float_to_int_cast.main.ConstProp.diff
+
+// `as` casts from floats to integers saturate, and const propagation has to agree with that.
+fn main() {
+    let high = 300.0f32 as u8;
+
+    let low = -1.0f32 as u8;
+
+    let nan = f32::NAN as i32;
+}
@@ -0,0 +1,15 @@
+// unit-test: ConstProp
+// Check that int-to-float casts are folded to the correctly rounded value.
+
+// EMIT_MIR int_to_float_cast.main.ConstProp.diff
+fn main() {
+    // CHECK-LABEL: fn main(
+    // CHECK: debug exact => [[exact:_.*]];
+    // CHECK: debug rounded => [[rounded:_.*]];
+    // CHECK: [[exact]] = const 3f64;
+    // CHECK: [[rounded]] = const 16777216f32;
+    let exact = 3i32 as f64;
+
+    // 2^24 + 1 is not representable as an `f32`, and rounds to even.
+    let rounded = 16777217i32 as f32;
+}
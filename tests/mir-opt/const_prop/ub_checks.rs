@@ -0,0 +1,27 @@
+// unit-test: ConstProp
+// compile-flags: -Cdebug-assertions=yes
+// Check that `UbChecks()` is not folded, as its value depends on the crate being codegened and not
+// on the one it is defined in.
+#![feature(custom_mir, core_intrinsics)]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR ub_checks.ub_checks.ConstProp.diff
+#[custom_mir(dialect = "analysis")]
+fn ub_checks() -> bool {
+    // CHECK-LABEL: fn ub_checks(
+    // CHECK: [[checks:_.*]] = UbChecks();
+    // CHECK: _0 = [[checks]];
+    mir!(
+        let checks: bool;
+        {
+            checks = UbChecks();
+            RET = checks;
+            Return()
+        }
+    )
+}
+
+fn main() {
+    ub_checks();
+}
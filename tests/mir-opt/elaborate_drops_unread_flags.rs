@@ -0,0 +1,119 @@
+// unit-test: ElaborateDrops
+#![feature(custom_mir, core_intrinsics)]
+
+// Check that drop flags which are never read are not assigned: either because an open drop only
+// tests the flags of the fields, or because the flag always holds the same value where it is
+// tested.
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+pub struct Pair {
+    a: Box<u32>,
+    b: Box<u32>,
+}
+
+// EMIT_MIR elaborate_drops_unread_flags.partial_move.ElaborateDrops.diff
+#[custom_mir(dialect = "built")]
+pub fn partial_move(c: bool, d: bool, a: Box<u32>, b: Box<u32>) {
+    // The flag of `p` is still declared: removing it would renumber the flags declared after it,
+    // so it is left for `SimplifyLocals` to clean up.
+    // CHECK-LABEL: fn partial_move(
+    // CHECK: let mut [[p_flag:_.*]]: bool;
+    // CHECK: let mut {{_.*}}: bool;
+    // CHECK: let mut {{_.*}}: bool;
+    // CHECK-NOT: [[p_flag]] = const
+    mir!(
+        let p: Pair;
+        let x: Box<u32>;
+        {
+            match c {
+                true => init,
+                _ => drop_p,
+            }
+        }
+
+        init = {
+            p = Pair { a: Move(a), b: Move(b) };
+            match d {
+                true => move_a,
+                _ => move_b,
+            }
+        }
+
+        move_a = {
+            x = Move(Field(p, 0));
+            Goto(drop_p)
+        }
+
+        move_b = {
+            x = Move(Field(p, 1));
+            Goto(drop_p)
+        }
+
+        drop_p = {
+            Drop(p, done, UnwindContinue())
+        }
+
+        done = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR elaborate_drops_unread_flags.variant_move.ElaborateDrops.diff
+#[custom_mir(dialect = "built")]
+pub fn variant_move(c: bool, o: Option<Box<u32>>) {
+    // `(o as Some).0` is maybe-uninitialized when `o` is dropped, as `o` may be `None`. But the
+    // open drop only drops it when `o` is `Some`, and then it is always initialized: its flag is
+    // always set when tested, so the test is replaced with a `goto`.
+    // CHECK-LABEL: fn variant_move(
+    // CHECK: bb3: {
+    // CHECK-NEXT: goto -> bb19;
+    // CHECK: bb15: {
+    // CHECK-NEXT: drop(((_2 as Some).0: std::boxed::Box<u32>)) -> [return: bb7, unwind: bb6];
+    // CHECK: bb16: {
+    // CHECK-NEXT: goto -> bb15;
+    // CHECK: bb19: {
+    // CHECK: switchInt(move {{_.*}}) -> [1: bb14, otherwise: bb17];
+    mir!(
+        let x: Box<u32>;
+        {
+            let d = Discriminant(o);
+            match d {
+                0 => drop_o,
+                1 => some,
+                _ => invalid,
+            }
+        }
+
+        some = {
+            match c {
+                true => move_some,
+                _ => drop_o,
+            }
+        }
+
+        move_some = {
+            x = Move(Field(Variant(o, 1), 0));
+            Goto(done)
+        }
+
+        drop_o = {
+            Drop(o, done, UnwindContinue())
+        }
+
+        done = {
+            Return()
+        }
+
+        invalid = {
+            Unreachable()
+        }
+    )
+}
+
+fn main() {
+    partial_move(true, false, Box::new(1), Box::new(2));
+    variant_move(false, Some(Box::new(1)));
+}
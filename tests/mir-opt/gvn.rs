@@ -597,6 +597,26 @@ fn fn_pointers() {
     opaque(cg);
 }
 
+/// Verify that a fn pointer compares equal to itself, even through a cast, but that we do not
+/// assume anything about two distinct fn pointers.
+#[custom_mir(dialect = "analysis")]
+fn fn_pointer_comparison(f: fn(u8) -> u8, g: fn(u8) -> u8) -> (bool, bool, bool) {
+    // CHECK-LABEL: fn fn_pointer_comparison(
+    // CHECK: [[same:_.*]] = const true;
+    // CHECK: [[other:_.*]] = Eq(_1, _2);
+    // CHECK: [[ptr:_.*]] = _1 as *const () (FnPtrToPtr);
+    // CHECK: [[cast:_.*]] = const true;
+    // CHECK: _0 = (const true, [[other]], const true);
+    mir!({
+        let same = f == f;
+        let other = f == g;
+        let ptr = f as *const ();
+        let cast = ptr == ptr;
+        RET = (same, other, cast);
+        Return()
+    })
+}
+
 /// Verify that we do not create a `ConstValue::Indirect` backed by a static's AllocId.
 #[custom_mir(dialect = "analysis")]
 fn indirect_static() {
@@ -626,6 +646,7 @@ fn main() {
     assert_eq!(direct, indirect);
     repeat();
     fn_pointers();
+    fn_pointer_comparison(identity, identity);
     indirect_static();
 }
 
@@ -652,4 +673,5 @@ fn identity<T>(x: T) -> T {
 // EMIT_MIR gvn.duplicate_slice.GVN.diff
 // EMIT_MIR gvn.repeat.GVN.diff
 // EMIT_MIR gvn.fn_pointers.GVN.diff
+// EMIT_MIR gvn.fn_pointer_comparison.GVN.diff
 // EMIT_MIR gvn.indirect_static.GVN.diff
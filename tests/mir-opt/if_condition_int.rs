@@ -1,6 +1,7 @@
 // skip-filecheck
 // unit-test: SimplifyComparisonIntegral
 // EMIT_MIR if_condition_int.opt_u32.SimplifyComparisonIntegral.diff
+// EMIT_MIR if_condition_int.opt_u64_ne.SimplifyComparisonIntegral.diff
 // EMIT_MIR if_condition_int.opt_negative.SimplifyComparisonIntegral.diff
 // EMIT_MIR if_condition_int.opt_char.SimplifyComparisonIntegral.diff
 // EMIT_MIR if_condition_int.opt_i8.SimplifyComparisonIntegral.diff
@@ -13,6 +14,11 @@ fn opt_u32(x: u32) -> u32 {
     if x == 42 { 0 } else { 1 }
 }
 
+// the targets are swapped for `!=`, and the switch keeps the type of the compared integer
+fn opt_u64_ne(x: u64) -> u32 {
+    if x != 7 { 0 } else { 1 }
+}
+
 // don't opt: it is already optimal to switch on the bool
 fn dont_opt_bool(x: bool) -> u32 {
     if x { 0 } else { 1 }
@@ -56,6 +62,7 @@ fn dont_opt_floats(a: f32) -> i32 {
 
 fn main() {
     opt_u32(0);
+    opt_u64_ne(0);
     opt_char('0');
     opt_i8(22);
     dont_opt_bool(false);
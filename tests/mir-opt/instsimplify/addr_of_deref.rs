@@ -0,0 +1,22 @@
+// unit-test: InstSimplify
+
+#![crate_type = "lib"]
+
+use std::ptr::{addr_of, addr_of_mut};
+
+// EMIT_MIR addr_of_deref.thin.InstSimplify.diff
+pub unsafe fn thin(p: *const u32, q: *mut u32) -> (*const u32, *mut u32) {
+    // CHECK-LABEL: fn thin(
+    // CHECK: [[p:_.*]] = _1;
+    // CHECK: [[q:_.*]] = _2;
+    // CHECK: _0 = (move [[p]], move [[q]]);
+    (addr_of!(*p), addr_of_mut!(*q))
+}
+
+// The metadata of wide pointers is left alone.
+// EMIT_MIR addr_of_deref.wide.InstSimplify.diff
+pub unsafe fn wide(p: *const [u32]) -> *const [u32] {
+    // CHECK-LABEL: fn wide(
+    // CHECK: _0 = &raw const (*_1);
+    addr_of!(*p)
+}
@@ -0,0 +1,58 @@
+// unit-test: InstSimplify
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+#![feature(custom_mir)]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR canonicalize_operands.add.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn add(x: i32) -> i32 {
+    // CHECK-LABEL: fn add(
+    // CHECK: _0 = Add(_1, const 1_i32);
+    mir! {
+        {
+            RET = 1 + x;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR canonicalize_operands.eq.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn eq(x: i32) -> bool {
+    // CHECK-LABEL: fn eq(
+    // CHECK: _0 = Eq(_1, const 2_i32);
+    mir! {
+        {
+            RET = 2 == x;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR canonicalize_operands.lt.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn lt(x: i32) -> bool {
+    // CHECK-LABEL: fn lt(
+    // CHECK: _0 = Gt(_1, const 3_i32);
+    mir! {
+        {
+            RET = 3 < x;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR canonicalize_operands.sub.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn sub(x: i32) -> i32 {
+    // CHECK-LABEL: fn sub(
+    // CHECK: _0 = Sub(const 4_i32, _1);
+    mir! {
+        {
+            RET = 4 - x;
+            Return()
+        }
+    }
+}
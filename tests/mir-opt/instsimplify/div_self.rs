@@ -0,0 +1,43 @@
+// unit-test: InstSimplify
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+#![feature(custom_mir)]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR div_self.guarded.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn guarded(x: u32) -> u32 {
+    // CHECK-LABEL: fn guarded(
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = const 1_u32;
+    mir! {
+        {
+            match x {
+                0 => zero,
+                _ => nonzero,
+            }
+        }
+        zero = {
+            RET = 0;
+            Return()
+        }
+        nonzero = {
+            RET = x / x;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR div_self.unguarded.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn unguarded(x: u32) -> u32 {
+    // CHECK-LABEL: fn unguarded(
+    // CHECK: _0 = Div(_1, _1);
+    mir! {
+        {
+            RET = x / x;
+            Return()
+        }
+    }
+}
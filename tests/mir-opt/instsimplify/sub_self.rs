@@ -0,0 +1,32 @@
+// unit-test: InstSimplify
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+#![feature(custom_mir)]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR sub_self.integer.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn integer(x: i32) -> i32 {
+    // CHECK-LABEL: fn integer(
+    // CHECK: _0 = const 0_i32;
+    mir! {
+        {
+            RET = x - x;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR sub_self.float.InstSimplify.diff
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn float(x: f32) -> f32 {
+    // CHECK-LABEL: fn float(
+    // CHECK: _0 = Sub(_1, _1);
+    mir! {
+        {
+            RET = x - x;
+            Return()
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// unit-test: InstSimplify
+// compile-flags: -Zmir-enable-passes=+SimplifyCfg-final
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+#![crate_type = "lib"]
+
+mod private {
+    pub enum Never {}
+
+    // The private field hides that this is uninhabited from the callers outside of this module,
+    // so MIR building keeps the return edge of calls to `make_no_value`.
+    pub struct NoValue(Never);
+
+    #[inline(never)]
+    pub fn make_no_value() -> NoValue {
+        loop {}
+    }
+}
+
+#[inline(never)]
+fn opaque(x: u32) -> u32 {
+    x
+}
+
+// EMIT_MIR uninhabited_call.call.InstSimplify.diff
+// EMIT_MIR uninhabited_call.call.SimplifyCfg-final.diff
+pub fn call() -> u32 {
+    // CHECK-LABEL: fn call(
+    // CHECK: make_no_value() -> unwind
+    // CHECK-NOT: opaque
+    // CHECK-NOT: return;
+    let _value = private::make_no_value();
+    opaque(5)
+}
@@ -0,0 +1,21 @@
+// compile-flags: -O -C debuginfo=0 -Zmir-opt-level=2 -Zmir-enable-passes=+RemoveRedundantBoundsChecks
+// only-64bit
+// ignore-debug the standard library debug assertions leak into this test
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+#![crate_type = "lib"]
+
+// Once `Range::<usize>::next` is inlined, the loop condition implies the bounds check of
+// `slice[i]`. This is `slice_iter::range_loop`, with `RemoveRedundantBoundsChecks` enabled.
+
+// EMIT_MIR range_index_loop.range_loop.PreCodegen.after.mir
+pub fn range_loop<'a, T>(slice: &'a [T], f: impl Fn(usize, &T)) {
+    // CHECK-LABEL: fn range_loop(
+    // CHECK: [[i:_.*]] = (({{_.*}} as Some).0: usize);
+    // CHECK-NOT: assert(
+    // CHECK: = &(*_1){{\[}}[[i]]{{\]}};
+    for i in 0..slice.len() {
+        let x = &slice[i];
+        f(i, x)
+    }
+}
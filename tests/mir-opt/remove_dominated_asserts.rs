@@ -0,0 +1,79 @@
+// unit-test: RemoveDominatedAsserts
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+
+// EMIT_MIR remove_dominated_asserts.divide.RemoveDominatedAsserts.diff
+pub fn divide(x: u32, d: u32) -> u32 {
+    // CHECK-LABEL: fn divide(
+    // CHECK: bb0: {
+    // CHECK-NEXT: switchInt(_2) -> [0: [[zero:bb.*]], otherwise: [[nonzero:bb.*]]];
+    // CHECK: [[nonzero]]: {
+    // CHECK-NOT: assert
+    // CHECK: goto -> [[div:bb.*]];
+    // CHECK: [[div]]: {
+    // CHECK: _0 = Div(
+    match d {
+        0 => 0,
+        _ => x / d,
+    }
+}
+
+// EMIT_MIR remove_dominated_asserts.divide_some.RemoveDominatedAsserts.diff
+pub fn divide_some(x: u32, d: Option<u32>) -> u32 {
+    // CHECK-LABEL: fn divide_some(
+    // CHECK: debug n => [[n:_.*]];
+    // CHECK: switchInt(((_2 as Some).0: u32)) -> [0: {{bb.*}}, otherwise: [[nonzero:bb.*]]];
+    // CHECK: [[nonzero]]: {
+    // CHECK: [[n]] = ((_2 as Some).0: u32);
+    // CHECK-NOT: assert
+    // CHECK: goto -> [[div:bb.*]];
+    // CHECK: [[div]]: {
+    // CHECK: _0 = Div(
+    match d {
+        None => 0,
+        Some(0) => 0,
+        Some(n) => x / n,
+    }
+}
+
+// The divisor is modified after the switch, so it is not SSA.
+// EMIT_MIR remove_dominated_asserts.divide_mutated.RemoveDominatedAsserts.diff
+pub fn divide_mutated(x: u32, mut d: u32) -> u32 {
+    // CHECK-LABEL: fn divide_mutated(
+    // CHECK: switchInt(_2) -> [0: {{bb.*}}, otherwise: [[nonzero:bb.*]]];
+    // CHECK: [[nonzero]]: {
+    // CHECK: _2 = Sub(
+    // CHECK: assert(
+    match d {
+        0 => 0,
+        _ => {
+            d -= 1;
+            x / d
+        }
+    }
+}
+
+// The arms join before the division, so the block of the assertion has two predecessors and
+// neither switch edge dominates it.
+// EMIT_MIR remove_dominated_asserts.divide_rejoined.RemoveDominatedAsserts.diff
+pub fn divide_rejoined(x: u32, d: u32) -> u32 {
+    // CHECK-LABEL: fn divide_rejoined(
+    // CHECK: switchInt(_2) -> [0: [[zero:bb.*]], otherwise: [[nonzero:bb.*]]];
+    // CHECK: [[nonzero]]: {
+    // CHECK: goto -> [[join:bb.*]];
+    // CHECK: [[zero]]: {
+    // CHECK: goto -> [[join]];
+    // CHECK: [[join]]: {
+    // CHECK: assert(
+    let y = match d {
+        0 => 0,
+        _ => x,
+    };
+    y / d
+}
+
+fn main() {
+    divide(7, 2);
+    divide_some(7, Some(2));
+    divide_mutated(7, 3);
+    divide_rejoined(7, 2);
+}
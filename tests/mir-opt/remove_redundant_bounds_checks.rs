@@ -0,0 +1,86 @@
+// unit-test: RemoveRedundantBoundsChecks
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// compile-flags: -Zmir-enable-passes=+LowerSliceLenCalls
+
+// EMIT_MIR remove_redundant_bounds_checks.sum.RemoveRedundantBoundsChecks.diff
+pub fn sum(arr: [u32; 4]) -> u32 {
+    // CHECK-LABEL: fn sum(
+    // CHECK: debug i => [[i:_.*]];
+    // CHECK: [[cond:_.*]] = Lt(move {{_.*}}, const 4_usize);
+    // CHECK: switchInt(move [[cond]]) -> [0: {{bb.*}}, otherwise: [[body:bb.*]]];
+    // CHECK: [[body]]: {
+    // CHECK: {{_.*}} = [[i]];
+    // CHECK-NOT: assert
+    // CHECK: goto -> [[access:bb.*]];
+    // CHECK: [[access]]: {
+    // CHECK: = _1[{{_.*}}];
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 4 {
+        sum += arr[i];
+        i += 1;
+    }
+    sum
+}
+
+// EMIT_MIR remove_redundant_bounds_checks.modified.RemoveRedundantBoundsChecks.diff
+pub fn modified(arr: [u32; 4]) -> u32 {
+    // CHECK-LABEL: fn modified(
+    // CHECK: assert(
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 4 {
+        i += 1;
+        sum += arr[i];
+    }
+    sum
+}
+
+// EMIT_MIR remove_redundant_bounds_checks.pointer_write.RemoveRedundantBoundsChecks.diff
+pub unsafe fn pointer_write(arr: [u32; 4], mut i: usize) -> u32 {
+    // CHECK-LABEL: fn pointer_write(
+    // CHECK: (*{{_.*}}) = const 100_usize;
+    // CHECK: assert(
+    let p = std::ptr::addr_of_mut!(i);
+    if i < 4 {
+        *p = 100;
+        arr[i]
+    } else {
+        0
+    }
+}
+
+// What a `for i in 0..arr.len()` loop looks like once `Range::<usize>::next` is inlined.
+// EMIT_MIR remove_redundant_bounds_checks.range_loop.RemoveRedundantBoundsChecks.diff
+pub fn range_loop(arr: &[u32]) -> u32 {
+    // CHECK-LABEL: fn range_loop(
+    // CHECK: switchInt(move {{_.*}}) -> [0: {{bb.*}}, 1: [[some:bb.*]], otherwise: {{bb.*}}];
+    // CHECK: [[some]]: {
+    // CHECK-NOT: assert
+    // CHECK: goto -> [[access:bb.*]];
+    // CHECK: [[access]]: {
+    // CHECK: = (*_1)[{{_.*}}];
+    let mut sum = 0;
+    let mut iter = 0..arr.len();
+    loop {
+        let next = if iter.start < iter.end {
+            let i = iter.start;
+            iter.start += 1;
+            Some(i)
+        } else {
+            None
+        };
+        match next {
+            Some(i) => sum += arr[i],
+            None => break,
+        }
+    }
+    sum
+}
+
+fn main() {
+    sum([1, 2, 3, 4]);
+    modified([1, 2, 3, 4]);
+    unsafe { pointer_write([1, 2, 3, 4], 0) };
+    range_loop(&[1, 2, 3, 4]);
+}
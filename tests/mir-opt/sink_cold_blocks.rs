@@ -0,0 +1,61 @@
+// unit-test: SinkColdBlocks
+// Check that blocks which are only reached through cold code, or which only lead to cold code,
+// are moved after the hot blocks.
+#![crate_type = "lib"]
+#![feature(custom_mir, core_intrinsics)]
+use std::intrinsics::mir::*;
+
+#[cold]
+#[inline(never)]
+fn cold_panic() {}
+
+// EMIT_MIR sink_cold_blocks.sink.SinkColdBlocks.after.mir
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn sink(x: bool) {
+    // CHECK-LABEL: fn sink(
+    // CHECK: bb0: {
+    // CHECK-NEXT: switchInt(_1) -> [1: bb2, otherwise: bb1];
+    // CHECK: bb1: {
+    // CHECK-NEXT: return;
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = cold_panic() -> [return: bb3, unwind unreachable];
+    // CHECK: bb3: {
+    // CHECK-NEXT: return;
+    mir!(
+        {
+            match x { true => panic, _ => ret }
+        }
+        panic = {
+            Call(RET = cold_panic(), done, UnwindUnreachable())
+        }
+        done = {
+            Return()
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// EMIT_MIR sink_cold_blocks.rejoin.SinkColdBlocks.after.mir
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn rejoin(x: bool) {
+    // CHECK-LABEL: fn rejoin(
+    // CHECK: bb0: {
+    // CHECK-NEXT: switchInt(_1) -> [1: bb2, otherwise: bb1];
+    // CHECK: bb1: {
+    // CHECK-NEXT: return;
+    // CHECK: bb2: {
+    // CHECK-NEXT: _0 = cold_panic() -> [return: bb1, unwind unreachable];
+    mir!(
+        {
+            match x { true => panic, _ => join }
+        }
+        panic = {
+            Call(RET = cold_panic(), join, UnwindUnreachable())
+        }
+        join = {
+            Return()
+        }
+    )
+}
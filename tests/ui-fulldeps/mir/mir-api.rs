@@ -0,0 +1,658 @@
+// run-pass
+//! Test the MIR APIs that need a type context, on MIR built by hand and on the optimized MIR of a
+//! small input crate, some of which is decoded from the metadata of an upstream crate.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_data_structures;
+extern crate rustc_driver;
+extern crate rustc_index;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
+extern crate rustc_span;
+extern crate rustc_target;
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_index::IndexVec;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::tcx::LenKind;
+use rustc_middle::mir::{
+    places_may_conflict, BinOp, BorrowKind, CastKind, ConstOperand, Local, LocalDecl,
+    MutBorrowKind, Mutability, Operand, Place, ProjectionElem, Rvalue, StatementKind,
+    TerminatorKind, RETURN_PLACE, START_BLOCK,
+};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_mir_dataflow::storage::storage_conflicts;
+use rustc_span::def_id::{LocalDefId, LOCAL_CRATE};
+use rustc_span::DUMMY_SP;
+use rustc_target::abi::{FieldIdx, VariantIdx};
+
+const UPSTREAM: &str = r#"
+#![feature(custom_mir, core_intrinsics)]
+use std::intrinsics::mir::*;
+
+pub fn ident(x: i32) -> i32 {
+    x
+}
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+pub fn tail_call(x: i32) -> i32 {
+    mir!({
+        TailCall(ident(Move(x)))
+    })
+}
+"#;
+
+const INPUT: &str = r#"
+#![feature(custom_mir, core_intrinsics)]
+use std::intrinsics::mir::*;
+
+pub fn call_upstream() -> i32 {
+    upstream::tail_call(1)
+}
+
+pub struct Big {
+    pub a: [u64; 16],
+    pub b: [u64; 16],
+}
+
+#[inline(never)]
+fn callee(_: u8, _: &str, _: (i32, bool)) {}
+
+pub fn caller(s: &str, x: i32) {
+    callee(1, s, (x, true))
+}
+
+pub enum E {
+    A(u8),
+    B,
+}
+
+pub union U {
+    pub a: u8,
+    pub b: u16,
+}
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+pub fn disjoint(x: i32) -> i32 {
+    mir!(
+        let a: i32;
+        let b: i32;
+        let c: i32;
+        {
+            StorageLive(a);
+            a = x;
+            StorageLive(c);
+            c = a;
+            StorageDead(a);
+            StorageLive(b);
+            b = c;
+            RET = b;
+            StorageDead(b);
+            StorageDead(c);
+            Return()
+        }
+    )
+}
+"#;
+
+struct NoCallbacks;
+
+impl Callbacks for NoCallbacks {}
+
+struct Test;
+
+impl Callbacks for Test {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            test_tail_call_round_trip(tcx);
+            test_len_kind(tcx);
+            test_whole_local_copy(tcx);
+            test_erase_regions_of_ref(tcx);
+            test_const_zero_one(tcx);
+            test_accepts_type(tcx);
+            test_try_fold_binop(tcx);
+            test_operand_tys(tcx);
+            test_remap_locals(tcx);
+            test_projected_ty(tcx);
+            test_is_identity_cast(tcx);
+            test_checked_binop_result_ty(tcx);
+            test_null_ptr_const(tcx);
+            test_promote_to_constant(tcx);
+            test_storage_conflicts(tcx);
+            test_project_deeper(tcx);
+            test_field_projections(tcx);
+            test_place_ordering(tcx);
+        });
+        Compilation::Stop
+    }
+}
+
+/// The item of the input crate named `name`.
+fn item(tcx: TyCtxt<'_>, name: &str) -> LocalDefId {
+    tcx.hir_crate_items(()).definitions().find(|&def_id| tcx.def_path_str(def_id) == name).unwrap()
+}
+
+/// Test that a `TailCall` terminator survives being encoded into crate metadata and decoded by
+/// a downstream crate, and that it is hashed like the other terminators.
+fn test_tail_call_round_trip(tcx: TyCtxt<'_>) {
+    let upstream = *tcx
+        .crates(())
+        .iter()
+        .find(|&&krate| tcx.crate_name(krate).as_str() == "upstream")
+        .unwrap();
+    assert_ne!(upstream, LOCAL_CRATE);
+    let upstream_item = |name: &str| {
+        let child = tcx
+            .module_children(upstream.as_def_id())
+            .iter()
+            .find(|child| child.ident.as_str() == name)
+            .unwrap();
+        child.res.def_id()
+    };
+
+    let body = tcx.optimized_mir(upstream_item("tail_call"));
+    let kind = &body.basic_blocks[START_BLOCK].terminator().kind;
+    let TerminatorKind::TailCall { func, args, .. } = kind else {
+        panic!("expected a tail call, found {kind:?}");
+    };
+    assert_eq!(func.const_fn_def().map(|(def_id, _)| def_id), Some(upstream_item("ident")));
+    assert_eq!(args, &[Operand::Move(Place::from(Local::from_u32(1)))]);
+    assert_eq!(kind.successors().count(), 0);
+
+    let hash = |kind: &TerminatorKind<'_>| -> Fingerprint {
+        tcx.with_stable_hashing_context(|mut hcx| {
+            let mut hasher = StableHasher::new();
+            kind.hash_stable(&mut hcx, &mut hasher);
+            hasher.finish()
+        })
+    };
+    assert_eq!(hash(kind), hash(&kind.clone()));
+    assert_ne!(hash(kind), hash(&TerminatorKind::Return));
+    let TerminatorKind::TailCall { func, fn_span, .. } = kind.clone() else { unreachable!() };
+    assert_ne!(hash(kind), hash(&TerminatorKind::TailCall { func, args: vec![], fn_span }));
+}
+
+/// Test that `Rvalue::len_kind` tells the length of an array from the length of a slice.
+fn test_len_kind(tcx: TyCtxt<'_>) {
+    let u8 = tcx.types.u8;
+    let slice_ref = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, Ty::new_slice(tcx, u8));
+    let local_decls: IndexVec<Local, LocalDecl<'_>> =
+        [tcx.types.unit, Ty::new_array(tcx, u8, 4), slice_ref, u8]
+            .into_iter()
+            .map(|ty| LocalDecl::new(ty, DUMMY_SP))
+            .collect();
+    let [array, slice_ref, byte] = [1, 2, 3].map(|i| Place::from(Local::from_u32(i)));
+
+    let len_kind = |rvalue: Rvalue<'_>| rvalue.len_kind(&local_decls, tcx);
+    assert_eq!(len_kind(Rvalue::Len(array)), Some(LenKind::Array(4)));
+    assert_eq!(len_kind(Rvalue::Len(tcx.mk_place_deref(slice_ref))), Some(LenKind::Slice));
+    assert_eq!(len_kind(Rvalue::Len(byte)), None);
+    assert_eq!(len_kind(Rvalue::Use(Operand::Copy(array))), None);
+}
+
+/// Test that `StatementKind::is_whole_local_copy` does not recognize copies of a field or through
+/// a reference as copies of the whole local.
+fn test_whole_local_copy(tcx: TyCtxt<'_>) {
+    let ty::Adt(adt, args) = *tcx.type_of(item(tcx, "Big")).instantiate_identity().kind() else {
+        panic!("`Big` is not a struct");
+    };
+    let field_ty = adt.all_fields().next().unwrap().ty(tcx, args);
+
+    // `_1` and `_2` are `Big`s.
+    let [dst, src] = [1, 2].map(|i| Place::from(Local::from_u32(i)));
+    let assign = |dst, src| StatementKind::Assign(Box::new((dst, Rvalue::Use(src))));
+    assert_eq!(assign(dst, Operand::Copy(src)).is_whole_local_copy(), Some((dst.local, src.local)));
+
+    let field = |place| tcx.mk_place_field(place, FieldIdx::from_u32(0), field_ty);
+    assert_eq!(assign(dst, Operand::Copy(field(src))).is_whole_local_copy(), None);
+    assert_eq!(assign(field(dst), Operand::Move(field(src))).is_whole_local_copy(), None);
+    assert_eq!(assign(dst, Operand::Copy(tcx.mk_place_deref(src))).is_whole_local_copy(), None);
+}
+
+/// Test that `Rvalue::erase_regions_of_ref` erases the region of a `Ref`, leaving its borrow kind
+/// and borrowed place untouched, and leaves other rvalues alone.
+fn test_erase_regions_of_ref(tcx: TyCtxt<'_>) {
+    let re_static = tcx.lifetimes.re_static;
+    let re_erased = tcx.lifetimes.re_erased;
+    let local = Place::from(Local::from_u32(1));
+    let deref = tcx.mk_place_deref(Place::from(Local::from_u32(2)));
+
+    for kind in [BorrowKind::Shared, BorrowKind::Mut { kind: MutBorrowKind::Default }] {
+        for place in [local, deref] {
+            let mut rvalue = Rvalue::Ref(re_static, kind, place);
+            rvalue.erase_regions_of_ref(tcx);
+            assert_eq!(rvalue, Rvalue::Ref(re_erased, kind, place), "{kind:?} {place:?}");
+        }
+    }
+
+    let mut rvalue = Rvalue::Use(Operand::Copy(local));
+    rvalue.erase_regions_of_ref(tcx);
+    assert_eq!(rvalue, Rvalue::Use(Operand::Copy(local)));
+}
+
+/// Test that `Operand::is_const_zero` and `Operand::is_const_one` recognize zero and one constants
+/// of numeric types only.
+fn test_const_zero_one(tcx: TyCtxt<'_>) {
+    let types = &tcx.types;
+    // (type, bits of the constant, is_const_zero, is_const_one)
+    let cases = [
+        (types.u8, 0, true, false),
+        (types.u8, 1, false, true),
+        (types.u8, 2, false, false),
+        (types.i64, 0, true, false),
+        (types.i64, u128::from(u64::MAX), false, false),
+        (types.usize, 0, true, false),
+        (types.usize, 1, false, true),
+        (types.f32, 0.0f32.to_bits().into(), true, false),
+        (types.f32, 1.0f32.to_bits().into(), false, true),
+        // Only positive zero is recognized.
+        (types.f32, (-0.0f32).to_bits().into(), false, false),
+        (types.f64, 1.0f64.to_bits().into(), false, true),
+        // `true` has the bits of one, but is not a number.
+        (types.bool, 1, false, false),
+    ];
+    for (ty, bits, is_zero, is_one) in cases {
+        let scalar = Scalar::from_uint(bits, ty.primitive_size(tcx));
+        let operand = Operand::const_from_scalar(tcx, ty, scalar, DUMMY_SP);
+        assert_eq!(operand.is_const_zero(), is_zero, "{bits:#x}_{ty}");
+        assert_eq!(operand.is_const_one(), is_one, "{bits:#x}_{ty}");
+    }
+}
+
+/// Test that `BinOp::accepts_type` accepts the operand types of each kind of operation.
+fn test_accepts_type(tcx: TyCtxt<'_>) {
+    let types = tcx.types;
+    let ptr = Ty::new_imm_ptr(tcx, types.u8);
+    for (op, ty, accepted) in [
+        (BinOp::BitOr, types.f64, false),
+        (BinOp::BitOr, types.bool, true),
+        (BinOp::Shl, types.i32, true),
+        (BinOp::Shl, types.f32, false),
+        (BinOp::Offset, ptr, true),
+        (BinOp::Offset, types.usize, false),
+        (BinOp::Add, types.bool, false),
+        (BinOp::Add, types.f32, true),
+        (BinOp::Eq, ptr, true),
+        (BinOp::Lt, types.unit, false),
+    ] {
+        assert_eq!(op.accepts_type(ty), accepted, "{op:?} {ty:?}");
+    }
+}
+
+/// Test that `Rvalue::try_fold_binop` folds operations on constants, and does not fold the ones
+/// that would panic at runtime.
+fn test_try_fold_binop(tcx: TyCtxt<'_>) {
+    let int = |ty: Ty<'_>, value: i128| {
+        let size = ty.primitive_size(tcx);
+        let scalar = if ty.is_signed() {
+            Scalar::from_int(value, size)
+        } else {
+            Scalar::from_uint(value as u128, size)
+        };
+        Operand::const_from_scalar(tcx, ty, scalar, DUMMY_SP)
+    };
+    let fold = |op, ty, lhs, rhs| {
+        Rvalue::BinaryOp(op, Box::new((int(ty, lhs), int(ty, rhs)))).try_fold_binop()
+    };
+
+    let i32 = tcx.types.i32;
+    assert_eq!(fold(BinOp::Add, i32, 2, 3), Some(Rvalue::Use(int(i32, 5))));
+    assert_eq!(fold(BinOp::Div, i32, 10, 0), None);
+    assert_eq!(fold(BinOp::Rem, i32, 10, 0), None);
+
+    // `MIN / -1` and `MIN % -1` overflow at the width of the type, even though the remainder
+    // would be `0`.
+    for ty in [tcx.types.i8, i32, tcx.types.i128] {
+        let min = ty.primitive_size(tcx).signed_int_min();
+        assert_eq!(fold(BinOp::Div, ty, min, -1), None, "{ty}");
+        assert_eq!(fold(BinOp::Rem, ty, min, -1), None, "{ty}");
+        let max = -(min + 1);
+        assert_eq!(fold(BinOp::Div, ty, min + 1, -1), Some(Rvalue::Use(int(ty, max))), "{ty}");
+        assert_eq!(fold(BinOp::Rem, ty, min + 1, -1), Some(Rvalue::Use(int(ty, 0))), "{ty}");
+    }
+
+    // The same bits are not `-1` in an unsigned type.
+    let u8 = tcx.types.u8;
+    assert_eq!(fold(BinOp::Div, u8, 128, 255), Some(Rvalue::Use(int(u8, 0))));
+    assert_eq!(fold(BinOp::Rem, u8, 128, 255), Some(Rvalue::Use(int(u8, 128))));
+}
+
+/// Test that `Operand::tys` gives the types of the arguments of a call.
+fn test_operand_tys(tcx: TyCtxt<'_>) {
+    let callee = item(tcx, "callee").to_def_id();
+    let body = tcx.optimized_mir(item(tcx, "caller"));
+    let args = body
+        .basic_blocks
+        .iter()
+        .find_map(|block| match &block.terminator().kind {
+            TerminatorKind::Call { func, args, .. }
+                if func.const_fn_def().map(|(def_id, _)| def_id) == Some(callee) =>
+            {
+                Some(args)
+            }
+            _ => None,
+        })
+        .expect("`caller` does not call `callee`");
+
+    let types = tcx.types;
+    let str_ref = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, types.str_);
+    let pair = Ty::new_tup(tcx, &[types.i32, types.bool]);
+    assert_eq!(Operand::tys(args, body, tcx).collect::<Vec<_>>(), [types.u8, str_ref, pair]);
+    assert_eq!(Operand::tys(&args[1..2], body, tcx).collect::<Vec<_>>(), [str_ref]);
+    assert_eq!(Operand::tys(&[], body, tcx).count(), 0);
+}
+
+/// Test that `Rvalue::remap_locals` remaps the locals of the operands, including the ones used as
+/// indices.
+fn test_remap_locals(tcx: TyCtxt<'_>) {
+    let local = Local::from_u32;
+    let map = |l: Local| local(l.as_u32() + 10);
+    let binop = |lhs: Local, array: Local, index: Local| {
+        let rhs = tcx.mk_place_index(tcx.mk_place_deref(Place::from(array)), index);
+        Rvalue::BinaryOp(
+            BinOp::Add,
+            Box::new((Operand::Copy(Place::from(lhs)), Operand::Move(rhs))),
+        )
+    };
+
+    let mut rvalue = binop(local(1), local(2), local(3));
+    rvalue.remap_locals(map, tcx);
+    assert_eq!(rvalue, binop(local(11), local(12), local(13)));
+
+    // The index local is remapped even when the base is left alone.
+    let mut rvalue = binop(local(1), local(2), local(3));
+    rvalue.remap_locals(|l| if l == local(3) { local(4) } else { l }, tcx);
+    assert_eq!(rvalue, binop(local(1), local(2), local(4)));
+}
+
+/// Test that `ProjectionElem::projected_ty` gives the type of each kind of projection.
+fn test_projected_ty(tcx: TyCtxt<'_>) {
+    let e = tcx.type_of(item(tcx, "E")).instantiate_identity();
+
+    let types = tcx.types;
+    let (u8, u16) = (types.u8, types.u16);
+    let u8_ref = Ty::new_imm_ref(tcx, tcx.lifetimes.re_static, u8);
+    let erased_u8_ref = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, u8);
+    let array = Ty::new_array(tcx, u16, 4);
+    let slice = Ty::new_slice(tcx, u16);
+    let pair = Ty::new_tup(tcx, &[u8, types.i32]);
+
+    let cases = [
+        (ProjectionElem::Deref, u8_ref, u8),
+        (ProjectionElem::Deref, Ty::new_mut_ptr(tcx, array), array),
+        (ProjectionElem::Field(FieldIdx::from_u32(1), types.i32), pair, types.i32),
+        (ProjectionElem::Index(Local::from_u32(1)), array, u16),
+        (ProjectionElem::Index(Local::from_u32(1)), slice, u16),
+        (ProjectionElem::ConstantIndex { offset: 1, min_length: 4, from_end: false }, array, u16),
+        (
+            ProjectionElem::Subslice { from: 1, to: 3, from_end: false },
+            array,
+            Ty::new_array(tcx, u16, 2),
+        ),
+        (ProjectionElem::Subslice { from: 1, to: 1, from_end: true }, slice, slice),
+        (ProjectionElem::Downcast(None, VariantIdx::from_u32(0)), e, e),
+        (ProjectionElem::OpaqueCast(erased_u8_ref), u8_ref, erased_u8_ref),
+        (ProjectionElem::Subtype(erased_u8_ref), u8_ref, erased_u8_ref),
+    ];
+    for (elem, base_ty, ty) in cases {
+        assert_eq!(elem.projected_ty(base_ty, tcx), ty, "{elem:?} of {base_ty:?}");
+    }
+}
+
+/// Test that `Rvalue::is_identity_cast` recognizes numeric casts to the type of their operand.
+fn test_is_identity_cast(tcx: TyCtxt<'_>) {
+    let types = tcx.types;
+    let ptr = Ty::new_imm_ptr(tcx, types.u8);
+    let local_decls: IndexVec<Local, LocalDecl<'_>> = [types.unit, types.i32, types.f64, ptr]
+        .into_iter()
+        .map(|ty| LocalDecl::new(ty, DUMMY_SP))
+        .collect();
+    let [x, y, p] = [1, 2, 3].map(|i| Operand::Copy(Place::from(Local::from_u32(i))));
+
+    let is_identity_cast = |kind, operand: &Operand<'_>, ty| {
+        Rvalue::Cast(kind, operand.clone(), ty).is_identity_cast(&local_decls, tcx)
+    };
+    assert!(is_identity_cast(CastKind::IntToInt, &x, types.i32));
+    assert!(!is_identity_cast(CastKind::IntToInt, &x, types.i64));
+    assert!(!is_identity_cast(CastKind::IntToInt, &x, types.u32));
+    assert!(is_identity_cast(CastKind::FloatToFloat, &y, types.f64));
+    assert!(!is_identity_cast(CastKind::FloatToFloat, &y, types.f32));
+    assert!(!is_identity_cast(CastKind::PtrToPtr, &p, ptr));
+    assert!(!Rvalue::Use(x).is_identity_cast(&local_decls, tcx));
+}
+
+/// Test that `Rvalue::checked_binop_result_ty` returns the `(T, bool)` type of a checked binary
+/// operation, and nothing for an unchecked one.
+fn test_checked_binop_result_ty(tcx: TyCtxt<'_>) {
+    let types = tcx.types;
+    let local_decls: IndexVec<Local, LocalDecl<'_>> = [types.unit, types.i32, types.i32, types.u8]
+        .into_iter()
+        .map(|ty| LocalDecl::new(ty, DUMMY_SP))
+        .collect();
+    let [x, y, shift] = [1, 2, 3].map(|i| Operand::Copy(Place::from(Local::from_u32(i))));
+    let result_ty = |rvalue: Rvalue<'_>| rvalue.checked_binop_result_ty(&local_decls, tcx);
+
+    let i32_with_overflow = Ty::new_tup(tcx, &[types.i32, types.bool]);
+    let checked = |op, lhs: &Operand<'_>, rhs: &Operand<'_>| {
+        Rvalue::CheckedBinaryOp(op, Box::new((lhs.clone(), rhs.clone())))
+    };
+    assert_eq!(result_ty(checked(BinOp::Add, &x, &y)), Some(i32_with_overflow));
+    assert_eq!(result_ty(checked(BinOp::Shl, &x, &shift)), Some(i32_with_overflow));
+    assert_eq!(result_ty(Rvalue::BinaryOp(BinOp::Add, Box::new((x.clone(), y)))), None);
+    assert_eq!(result_ty(Rvalue::Use(x)), None);
+}
+
+/// Test that `Operand::const_null_ptr` builds a null raw pointer constant, and that
+/// `Operand::is_null_ptr_const` recognizes it but not non-null pointers or null integers.
+fn test_null_ptr_const(tcx: TyCtxt<'_>) {
+    let u8 = tcx.types.u8;
+    let null = Operand::const_null_ptr(tcx, u8, Mutability::Not, DUMMY_SP);
+    assert_eq!(null.constant().unwrap().ty(), Ty::new_imm_ptr(tcx, u8));
+    assert!(null.is_null_ptr_const());
+    let null_mut = Operand::const_null_ptr(tcx, u8, Mutability::Mut, DUMMY_SP);
+    assert_eq!(null_mut.constant().unwrap().ty(), Ty::new_mut_ptr(tcx, u8));
+    assert!(null_mut.is_null_ptr_const());
+
+    let address = |ty, addr| {
+        Operand::const_from_scalar(tcx, ty, Scalar::from_target_usize(addr, &tcx), DUMMY_SP)
+    };
+    assert!(address(Ty::new_imm_ptr(tcx, u8), 0).is_null_ptr_const());
+    assert!(!address(Ty::new_imm_ptr(tcx, u8), 4).is_null_ptr_const());
+    assert!(!address(tcx.types.usize, 0).is_null_ptr_const());
+}
+
+/// Test that `Operand::promote_to_constant` replaces place and constant operands with the given
+/// constant, and that `Operand::as_constant_mut` changes a constant in place.
+fn test_promote_to_constant(tcx: TyCtxt<'_>) {
+    let constant = |value: i32| -> ConstOperand<'_> {
+        let operand =
+            Operand::const_from_scalar(tcx, tcx.types.i32, Scalar::from_i32(value), DUMMY_SP);
+        *operand.constant().unwrap()
+    };
+    let place = Place::from(Local::from_u32(1));
+
+    for mut operand in
+        [Operand::Copy(place), Operand::Move(place), Operand::Constant(Box::new(constant(1)))]
+    {
+        operand.promote_to_constant(constant(2));
+        assert_eq!(operand.constant(), Some(&constant(2)));
+        assert_eq!(operand.place(), None);
+    }
+
+    let mut operand = Operand::Constant(Box::new(constant(1)));
+    *operand.as_constant_mut().unwrap() = constant(3);
+    assert_eq!(operand, Operand::Constant(Box::new(constant(3))));
+}
+
+/// Test that `storage_conflicts` only reports locals whose storage overlaps as conflicting, and
+/// that locals without storage annotations conflict with everything.
+fn test_storage_conflicts(tcx: TyCtxt<'_>) {
+    let body = tcx.optimized_mir(item(tcx, "disjoint"));
+
+    // The locals in the order of their `StorageLive`s, rather than relying on their numbering.
+    let storage_live: Vec<_> = body.basic_blocks[START_BLOCK]
+        .statements
+        .iter()
+        .filter_map(|statement| match statement.kind {
+            StatementKind::StorageLive(local) => Some(local),
+            _ => None,
+        })
+        .collect();
+    let [a, c, b] = storage_live[..] else { panic!("unexpected storage markers in {body:?}") };
+    let (ret, x) = (Local::from_u32(0), Local::from_u32(1));
+
+    let conflicts = storage_conflicts(tcx, body);
+    let conflict = |l, r| {
+        assert_eq!(conflicts.contains(l, r), conflicts.contains(r, l), "{l:?} and {r:?}");
+        conflicts.contains(l, r)
+    };
+    assert!(!conflict(a, b));
+    assert!(conflict(a, c));
+    assert!(conflict(b, c));
+    for local in [a, b, c, ret, x] {
+        assert!(conflict(local, local), "{local:?}");
+        assert!(conflict(ret, local), "{local:?}");
+        assert!(conflict(x, local), "{local:?}");
+    }
+}
+
+/// Test that `Place::project_deeper` and `PlaceRef::project_deeper` append the projections to
+/// the ones of the base and intern the result.
+fn test_project_deeper(tcx: TyCtxt<'_>) {
+    let types = tcx.types;
+    let (local, index) = (Local::from_u32(1), Local::from_u32(2));
+    let field = ProjectionElem::Field(FieldIdx::from_u32(0), types.u8);
+    let elems = [ProjectionElem::Deref, ProjectionElem::Index(index), field];
+
+    // An empty base takes the projections as they are.
+    let place = Place::from(local).project_deeper(&elems, tcx);
+    assert_eq!(place, Place { local, projection: tcx.mk_place_elems(&elems) });
+    assert_eq!(place.as_ref().project_deeper(&[], tcx), place);
+    assert_eq!(Place::from(local).project_deeper(&[], tcx), Place::from(local));
+
+    // A base with projections keeps them in front of the new ones.
+    let base = Place::from(local).project_deeper(&elems[..1], tcx);
+    assert_eq!(base.project_deeper(&elems[1..], tcx), place);
+    assert_eq!(base.as_ref().project_deeper(&elems[1..], tcx), place);
+    assert_eq!(base.project_deeper(&[], tcx), base);
+    assert!(std::ptr::eq(base.project_deeper(&elems[1..], tcx).projection, place.projection));
+}
+
+/// Test the `Place` APIs on field projections, which the unit tests of `rustc_middle` replace with
+/// constant indices as they have no type context to build the type of a field.
+fn test_field_projections<'tcx>(tcx: TyCtxt<'tcx>) {
+    let types = tcx.types;
+    let pair = Ty::new_tup(tcx, &[types.u8, types.u16]);
+    let union = tcx.type_of(item(tcx, "U")).instantiate_identity();
+    let pair_ref = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, pair);
+    let local_decls: IndexVec<Local, LocalDecl<'_>> =
+        [pair, pair, union, pair_ref].into_iter().map(|ty| LocalDecl::new(ty, DUMMY_SP)).collect();
+    let [b, u, r] = [1, 2, 3].map(Local::from_u32);
+    let (x, y) = (
+        ProjectionElem::Field(FieldIdx::from_u32(0), types.u8),
+        ProjectionElem::Field(FieldIdx::from_u32(1), types.u16),
+    );
+    let place =
+        |local, projection: &[_]| Place { local, projection: tcx.mk_place_elems(projection) };
+
+    assert_eq!(place(b, &[x]).as_local(), None);
+    assert_eq!(place(b, &[x]).local_or_deref_local(), None);
+    assert!(!place(b, &[x]).is_indirect());
+    assert!(!place(RETURN_PLACE, &[x]).is_return_place());
+    let deref_x = place(r, &[ProjectionElem::Deref, x]);
+    assert_eq!(deref_x.local_or_deref_local(), None);
+    assert_eq!(deref_x.is_deref_of_local(), None);
+    assert!(deref_x.is_indirect());
+
+    let deref = place(r, &[ProjectionElem::Deref]);
+    let deref_y = place(r, &[ProjectionElem::Deref, y]);
+    assert_eq!(deref_y.as_ref().last_projection(), Some((deref.as_ref(), y)));
+    assert_eq!(deref_y.truncate(1, tcx), deref);
+
+    let conflict = |p: Place<'tcx>, q: Place<'tcx>| {
+        places_may_conflict(tcx, &local_decls, p.as_ref(), q.as_ref())
+    };
+    assert!(!conflict(place(b, &[x]), place(b, &[y])));
+    assert!(conflict(place(b, &[x]), place(b, &[x])));
+    assert!(conflict(Place::from(b), place(b, &[y])));
+    assert!(!conflict(place(RETURN_PLACE, &[x]), place(b, &[x])));
+    // The fields of a union overlap.
+    assert!(conflict(place(u, &[x]), place(u, &[y])));
+    assert!(conflict(deref_x, place(b, &[y])));
+}
+
+/// Test that places are ordered by their local, then by the kind, field, index or variant of each
+/// of their projections, and only by the types of their projections when nothing else differs.
+fn test_place_ordering(tcx: TyCtxt<'_>) {
+    let types = tcx.types;
+    let [_1, _2] = [1, 2].map(Local::from_u32);
+    let place =
+        |local, projection: &[_]| Place { local, projection: tcx.mk_place_elems(projection) };
+    let field = |index, ty| ProjectionElem::Field(FieldIdx::from_u32(index), ty);
+    let deref = ProjectionElem::Deref;
+
+    let sorted = [
+        Place::from(_1),
+        place(_1, &[deref]),
+        place(_1, &[deref, field(0, types.u8)]),
+        place(_1, &[deref, field(0, types.u128)]),
+        place(_1, &[field(0, types.u128)]),
+        place(_1, &[field(1, types.u8)]),
+        place(_1, &[ProjectionElem::Index(_2)]),
+        place(_1, &[ProjectionElem::Downcast(None, VariantIdx::from_u32(0))]),
+        place(_1, &[ProjectionElem::Downcast(None, VariantIdx::from_u32(1))]),
+        Place::from(_2),
+    ];
+    for (i, a) in sorted.iter().enumerate() {
+        for (j, b) in sorted.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j), "{a:?} and {b:?}");
+            assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)), "{a:?} and {b:?}");
+        }
+    }
+
+    let mut places = sorted.to_vec();
+    places.reverse();
+    places.extend_from_slice(&sorted);
+    places.sort();
+    places.dedup();
+    assert_eq!(places, sorted);
+}
+
+fn run(args: &[&str], callbacks: &mut (dyn Callbacks + Send)) {
+    let args: Vec<_> = ["rustc"].iter().chain(args).map(|arg| arg.to_string()).collect();
+    rustc_driver::catch_fatal_errors(|| RunCompiler::new(&args, callbacks).run()).unwrap().unwrap();
+}
+
+fn main() {
+    std::fs::write("upstream.rs", UPSTREAM).unwrap();
+    std::fs::write("mir_api_input.rs", INPUT).unwrap();
+    run(
+        &[
+            "--crate-type=lib",
+            "--emit=metadata",
+            "-Zalways-encode-mir",
+            "-Zvalidate-mir",
+            "upstream.rs",
+        ],
+        &mut NoCallbacks,
+    );
+    run(
+        &["--crate-type=lib", "--extern", "upstream=libupstream.rmeta", "mir_api_input.rs"],
+        &mut Test,
+    );
+}
@@ -0,0 +1,55 @@
+// Check that raw pointers built from a data pointer and metadata round-trip, both at runtime
+// and during const evaluation.
+//
+// run-pass
+// compile-flags: -Zvalidate-mir
+#![feature(custom_mir, core_intrinsics)]
+use std::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+const fn thin(data: *const u8) -> *const u16 {
+    mir!(
+        {
+            RET = RawPtr(data, ());
+            Return()
+        }
+    )
+}
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+const fn slice(data: *mut u8, len: usize) -> *mut [u8] {
+    mir!(
+        {
+            RET = RawPtr(data, len);
+            Return()
+        }
+    )
+}
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+const fn string(data: *const u8, len: usize) -> *const str {
+    mir!(
+        {
+            RET = RawPtr(data, len);
+            Return()
+        }
+    )
+}
+
+const HELLO: &str = unsafe { &*string("hello world".as_ptr(), 5) };
+
+fn main() {
+    let mut buf = [1_u8, 2, 3, 4];
+
+    let p = thin(buf.as_ptr());
+    assert_eq!(p.cast::<u8>(), buf.as_ptr());
+
+    let s = slice(buf.as_mut_ptr(), 3);
+    assert_eq!(s.cast::<u8>(), buf.as_mut_ptr());
+    assert_eq!(unsafe { &*s }, &[1, 2, 3]);
+    unsafe { (*s)[2] = 7 };
+    assert_eq!(buf, [1, 2, 7, 4]);
+
+    assert_eq!(HELLO, "hello");
+    assert_eq!(unsafe { &*string("hello world".as_ptr(), 5) }, "hello");
+}
@@ -0,0 +1,26 @@
+// Check that `UbChecks()` returns whether debug assertions are enabled, both at runtime and during
+// const evaluation.
+//
+// run-pass
+// revisions: yes no
+// compile-flags: -Zvalidate-mir
+//[yes] compile-flags: -Cdebug-assertions=yes
+//[no] compile-flags: -Cdebug-assertions=no
+#![feature(custom_mir, core_intrinsics)]
+use std::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+const fn ub_checks() -> bool {
+    mir!({
+        RET = UbChecks();
+        Return()
+    })
+}
+
+const UB_CHECKS: bool = ub_checks();
+
+fn main() {
+    assert_eq!(ub_checks(), cfg!(debug_assertions));
+    assert_eq!(UB_CHECKS, cfg!(debug_assertions));
+    assert_eq!(ub_checks(), cfg!(yes));
+}
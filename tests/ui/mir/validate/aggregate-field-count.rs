@@ -0,0 +1,30 @@
+// Check that the validator accepts the aggregates produced by MIR building.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir
+
+#![feature(coroutines)]
+
+struct Pair(u8, u16);
+
+enum Shape {
+    Empty,
+    Point { x: i32, y: i32 },
+}
+
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {
+    let pair = Pair(1, 2);
+    let tuple = (pair.0, pair.1, 3u32);
+    let _shapes = [Shape::Empty, Shape::Point { x: 1, y: 2 }];
+    let _ints: [i32; 3] = [1, tuple.2 as i32, 3];
+    let _bits = Bits { int: tuple.2 };
+    let closure = move || tuple.0 + pair.0;
+    let _coroutine = move || {
+        yield closure();
+    };
+}
@@ -0,0 +1,17 @@
+// Check that the validator rejects a dereference of a `Box` once `ElaborateBoxDerefs` has run.
+//
+// build-fail
+// compile-flags: -Zvalidate-mir --crate-type=lib
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: dereferenced after ElaborateBoxDerefs
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+pub fn read(b: Box<i32>) -> i32 {
+    mir!({
+        RET = *b;
+        Return()
+    })
+}
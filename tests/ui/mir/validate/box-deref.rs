@@ -0,0 +1,15 @@
+// Check that the validator accepts a dereference of a `Box` before `ElaborateBoxDerefs`, which
+// lowers it to a dereference of the pointer inside the box.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir --crate-type=lib
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn read(b: Box<i32>) -> i32 {
+    mir!({
+        RET = *b;
+        Return()
+    })
+}
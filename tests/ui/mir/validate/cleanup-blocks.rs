@@ -0,0 +1,25 @@
+// Check that the validator accepts a well-formed cleanup subgraph: cleanup blocks only jump to
+// other cleanup blocks, have no unwind edges of their own, and end in `UnwindResume`.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir --crate-type=lib
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn f() {
+    mir!(
+        {
+            Call(RET = f(), bb1, UnwindCleanup(bb2))
+        }
+        bb1 = {
+            Return()
+        }
+        bb2 (cleanup) = {
+            Call(RET = f(), bb3, UnwindTerminate(ReasonInCleanup))
+        }
+        bb3 (cleanup) = {
+            UnwindResume()
+        }
+    )
+}
@@ -0,0 +1,28 @@
+// Check that the validator rejects an unwind edge out of a cleanup block, since unwinding while
+// already unwinding aborts instead.
+//
+// build-fail
+// compile-flags: -Zvalidate-mir --crate-type=lib
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: `UnwindAction::Cleanup` in cleanup block
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn f() {
+    mir!(
+        {
+            Call(RET = f(), bb1, UnwindCleanup(bb2))
+        }
+        bb1 = {
+            Return()
+        }
+        bb2 (cleanup) = {
+            Call(RET = f(), bb3, UnwindCleanup(bb3))
+        }
+        bb3 (cleanup) = {
+            UnwindResume()
+        }
+    )
+}
@@ -0,0 +1,24 @@
+// Check that the validator rejects a `Return` from a cleanup block.
+//
+// build-fail
+// compile-flags: -Zvalidate-mir --crate-type=lib
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: Cannot `Return` from cleanup basic block
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn f() {
+    mir!(
+        {
+            Call(RET = f(), bb1, UnwindCleanup(bb2))
+        }
+        bb1 = {
+            Return()
+        }
+        bb2 (cleanup) = {
+            Return()
+        }
+    )
+}
@@ -0,0 +1,24 @@
+// Check that the validator rejects a normal edge from a cleanup block to a non-cleanup block.
+//
+// build-fail
+// compile-flags: -Zvalidate-mir --crate-type=lib
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: violates unwind invariants (cleanup true -> false)
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn f() {
+    mir!(
+        {
+            Call(RET = f(), bb1, UnwindCleanup(bb2))
+        }
+        bb1 = {
+            Return()
+        }
+        bb2 (cleanup) = {
+            Goto(bb1)
+        }
+    )
+}
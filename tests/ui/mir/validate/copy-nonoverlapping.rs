@@ -0,0 +1,28 @@
+// Check that the validator accepts `CopyNonOverlapping` statements whose `src` and `dst`
+// point to the same type, including after the intrinsic call has been lowered.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir -Zmir-opt-level=4
+#![feature(core_intrinsics)]
+use std::intrinsics::copy_nonoverlapping;
+
+pub fn copy_u32(src: &u32, dst: &mut u32) {
+    unsafe { copy_nonoverlapping(src, dst, 1) }
+}
+
+pub fn copy_array(src: &[u8; 16], dst: &mut [u8; 16], count: usize) {
+    unsafe { copy_nonoverlapping(src as *const [u8; 16], dst as *mut [u8; 16], count) }
+}
+
+pub fn copy_boxed(src: Box<(u8, u16)>, dst: &mut (u8, u16)) {
+    unsafe { copy_nonoverlapping(&*src, dst, 1) }
+}
+
+fn main() {
+    let mut a = 0;
+    copy_u32(&1, &mut a);
+    let mut b = [0; 16];
+    copy_array(&[1; 16], &mut b, 1);
+    let mut c = (0, 0);
+    copy_boxed(Box::new((1, 2)), &mut c);
+}
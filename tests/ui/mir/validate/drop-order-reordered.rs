@@ -0,0 +1,38 @@
+// Check that the validator rejects user variables that are dropped in declaration order.
+//
+// compile-flags: -Zvalidate-mir
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: which is declared after it
+#![feature(custom_mir, core_intrinsics)]
+extern crate core;
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "built")]
+pub fn reordered() {
+    mir!(
+        let a: String;
+        let b: String;
+        debug a => a;
+        debug b => b;
+        {
+            Call(a = String::new(), init_b, UnwindContinue())
+        }
+        init_b = {
+            Call(b = String::new(), drop_a, UnwindContinue())
+        }
+        drop_a = {
+            Drop(a, drop_b, UnwindContinue())
+        }
+        drop_b = {
+            Drop(b, done, UnwindContinue())
+        }
+        done = {
+            Return()
+        }
+    )
+}
+
+fn main() {
+    reordered();
+}
@@ -0,0 +1,28 @@
+// Check that the validator accepts the drop order produced by MIR building.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir
+
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("{}", self.0);
+    }
+}
+
+fn params(_a: Noisy, (_b, _): (Noisy, Noisy), _c: Noisy) {
+    let _d = Noisy("d");
+    {
+        let _e = Noisy("e");
+        let _f = Noisy("f");
+    }
+    let _g = Noisy("g");
+}
+
+fn main() {
+    params(Noisy("a"), (Noisy("b"), Noisy("_")), Noisy("c"));
+    match (Noisy("x"), Noisy("y")) {
+        (_x, _y) => {}
+    }
+}
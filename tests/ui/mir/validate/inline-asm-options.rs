@@ -0,0 +1,33 @@
+// Check that the validator accepts the destinations and unwind actions of inline assembly with
+// the `noreturn` and `may_unwind` options, including after `may_unwind` assembly is inlined into
+// a function that cannot unwind.
+//
+// build-pass
+// needs-asm-support
+// compile-flags: -Zvalidate-mir -Zmir-opt-level=3
+
+#![feature(asm_unwind)]
+
+use std::arch::asm;
+
+#[inline(always)]
+fn may_unwind() {
+    unsafe { asm!("", options(may_unwind)) };
+}
+
+fn diverge() -> ! {
+    unsafe { asm!("", options(noreturn)) }
+}
+
+extern "C" fn no_unwind() {
+    may_unwind();
+}
+
+fn main() {
+    unsafe { asm!("") };
+    may_unwind();
+    no_unwind();
+    if std::env::args().count() > 100 {
+        diverge();
+    }
+}
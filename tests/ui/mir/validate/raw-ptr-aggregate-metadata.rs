@@ -0,0 +1,24 @@
+// Check that the validator rejects raw pointer aggregates whose metadata does not match the
+// pointee.
+//
+// compile-flags: -Zvalidate-mir
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: RawPtr metadata for [u8] must be usize, but it is u32
+#![feature(custom_mir, core_intrinsics)]
+extern crate core;
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "optimized")]
+pub fn wrong_metadata(data: *const u8, len: u32) -> *const [u8] {
+    mir!(
+        {
+            RET = RawPtr(data, len);
+            Return()
+        }
+    )
+}
+
+fn main() {
+    wrong_metadata(core::ptr::null(), 0);
+}
@@ -0,0 +1,18 @@
+// Check that the validator rejects `SetDiscriminant` to a variant the enum does not have.
+//
+// build-fail
+// compile-flags: -Zvalidate-mir --crate-type=lib
+// failure-status: 101
+// dont-check-compiler-stderr
+// error-pattern: only has 2 variants
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn set_discr(option: &mut Option<()>) {
+    mir!({
+        Deinit(*option);
+        SetDiscriminant(*option, 2);
+        Return()
+    })
+}
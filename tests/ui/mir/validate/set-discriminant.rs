@@ -0,0 +1,20 @@
+// Check that the validator accepts `SetDiscriminant` to every variant of an enum, and to the only
+// variant of a struct.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir --crate-type=lib
+#![feature(custom_mir, core_intrinsics)]
+use core::intrinsics::mir::*;
+
+pub struct Unit;
+
+#[custom_mir(dialect = "runtime", phase = "initial")]
+pub fn set_discr(option: &mut Option<()>, unit: &mut Unit) {
+    mir!({
+        Deinit(*option);
+        SetDiscriminant(*option, 0);
+        SetDiscriminant(*option, 1);
+        SetDiscriminant(*unit, 0);
+        Return()
+    })
+}
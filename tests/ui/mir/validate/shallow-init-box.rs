@@ -0,0 +1,18 @@
+// Check that the validator accepts the `ShallowInitBox` produced for `#[rustc_box]`, whose operand
+// is the `*mut u8` returned by `exchange_malloc`.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir -Zmir-opt-level=3
+
+#![feature(rustc_attrs, stmt_expr_attributes)]
+
+fn boxed<T>(value: T) -> Box<T> {
+    #[rustc_box]
+    Box::new(value)
+}
+
+fn main() {
+    let _int = boxed(1u16);
+    let _pair = #[rustc_box]
+    Box::new((1u8, [0u64; 4]));
+}
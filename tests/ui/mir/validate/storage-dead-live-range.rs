@@ -0,0 +1,30 @@
+// Check that the validator accepts a local that is used again after its storage is restored.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "built")]
+fn reuse_after_storage_live() -> usize {
+    mir!(
+        let a: usize;
+        {
+            StorageLive(a);
+            a = 0;
+            StorageDead(a);
+            StorageLive(a);
+            a = 1;
+            RET = a;
+            StorageDead(a);
+            Return()
+        }
+    )
+}
+
+fn main() {
+    reuse_after_storage_live();
+}
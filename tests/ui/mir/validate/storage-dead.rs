@@ -0,0 +1,32 @@
+// compile-flags: -Zvalidate-mir -Ztreat-err-as-bug
+// failure-status: 101
+// error-pattern: broken MIR in
+// error-pattern: use of local _1, which has no storage here
+// normalize-stderr-test "note: .*\n\n" -> ""
+// normalize-stderr-test "thread 'rustc' panicked.*\n" -> ""
+// normalize-stderr-test "storage_dead\[....\]" -> "storage_dead[HASH]"
+// rustc-env:RUST_BACKTRACE=0
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+use core::ptr::{addr_of, addr_of_mut};
+
+#[custom_mir(dialect = "built")]
+fn use_after_storage_dead() -> usize {
+    mir!(
+        let a: usize;
+        {
+            StorageLive(a);
+            a = 0;
+            StorageDead(a);
+            RET = a;
+            Return()
+        }
+    )
+}
+
+fn main() {
+    use_after_storage_dead();
+}
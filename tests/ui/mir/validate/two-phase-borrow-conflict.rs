@@ -0,0 +1,16 @@
+// Checks that a conflicting use between the reservation and the activation of a two-phase borrow
+// is reported by borrowck rather than turned into an ICE by MIR validation.
+// compile-flags: -Zvalidate-mir
+
+struct Foo {}
+
+impl Foo {
+    fn method(&mut self, _foo: &mut Foo) {}
+}
+
+fn main() {
+    let mut foo = Foo {};
+    foo.method(&mut foo);
+    //~^ ERROR cannot borrow `foo` as mutable more than once at a time
+    //~| ERROR cannot borrow `foo` as mutable more than once at a time
+}
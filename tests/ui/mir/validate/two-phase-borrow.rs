@@ -0,0 +1,48 @@
+// Checks that well-formed two-phase borrows pass MIR validation.
+// build-pass
+// compile-flags: -Zvalidate-mir
+
+struct S {
+    a: Vec<usize>,
+    b: usize,
+}
+
+fn nested_read(v: &mut Vec<usize>) {
+    v.push(v.len());
+}
+
+fn disjoint_field(s: &mut S) {
+    s.a.push({
+        s.b += 1;
+        s.b
+    });
+}
+
+fn across_blocks(v: &mut Vec<usize>, c: bool) {
+    v.push(if c { v.len() } else { v.capacity() });
+}
+
+fn in_loop(v: &mut Vec<usize>) {
+    for _ in 0..3 {
+        v.push(v.len());
+    }
+}
+
+// Overwriting the reference does not touch the vector the reservation points to.
+fn overwrite_base<'a>(mut v: &'a mut Vec<usize>, o: &'a mut Vec<usize>) {
+    v.push({
+        v = o;
+        1
+    });
+}
+
+fn main() {
+    let mut v = vec![];
+    nested_read(&mut v);
+    across_blocks(&mut v, true);
+    in_loop(&mut v);
+    let mut s = S { a: v, b: 0 };
+    disjoint_field(&mut s);
+    let mut o = vec![];
+    overwrite_base(&mut s.a, &mut o);
+}